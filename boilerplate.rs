@@ -1,16 +1,32 @@
-use std::env;
 use std::error::Error;
 
 use log::debug;
 
-use utils::{read_input, set_logging_level};
+use utils::Solution;
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let args: Vec<String> = env::args().collect();
-    set_logging_level(&args);
-    let input = read_input(&args);
+struct Day;
+
+impl Solution for Day {
+    type Parsed = Vec<String>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Box<dyn Error>> {
+        Ok(input.lines().map(String::from).collect())
+    }
+
+    fn part_1(parsed: &Self::Parsed) -> usize {
+        debug!("Lines: {}", parsed.len());
+        0
+    }
 
-    Ok(())
+    fn part_2(_parsed: &Self::Parsed) -> usize {
+        0
+    }
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    utils::run::<Day>()
 }
 
 #[cfg(test)]
@@ -19,6 +35,7 @@ mod tests {
 
     #[test]
     fn test_sample() {
-        assert!(true)
+        let answers = utils::solve::<Day>("").unwrap();
+        assert_eq!(answers.part_1, "0");
     }
 }
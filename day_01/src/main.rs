@@ -1,13 +1,35 @@
-use std::fs;
-use std::env;
 use std::collections::HashMap;
+use std::error::Error;
 
 use log::debug;
 use regex::Regex;
 
-use utils::{read_input, set_logging_level};
+use utils::Solution;
 
-fn parse_digits(input: &str) -> Result<Vec<i32>, Box<dyn std::error::Error>> {
+struct Day01;
+
+impl Solution for Day01 {
+    // Each part reads the digits of the lines differently
+    type Parsed = String;
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Box<dyn Error>> {
+        Ok(input.to_string())
+    }
+
+    fn part_1(input: &Self::Parsed) -> i32 {
+        let digits_pt1 = parse_digits(input);
+        debug!("{:?}", digits_pt1);
+        digits_pt1.iter().sum()
+    }
+
+    fn part_2(input: &Self::Parsed) -> i32 {
+        parse_digits_regex(input).iter().sum()
+    }
+}
+
+fn parse_digits(input: &str) -> Vec<i32> {
     let mut digits: Vec<i32> = vec![];
     for row in input.lines() {
         let mut row_digits: Vec<char> = vec![];
@@ -16,14 +38,13 @@ fn parse_digits(input: &str) -> Result<Vec<i32>, Box<dyn std::error::Error>> {
                 row_digits.push(c);
             }
         }
-        let d: i32 = format!("{}{}", row_digits[0], row_digits.last().unwrap()).parse()?;
-        digits.push(d);
+        digits.push(two_digits(row_digits[0], *row_digits.last().unwrap()));
     }
-    Ok(digits)
+    digits
 }
 
-fn parse_digits_regex(input: &str) -> Result<Vec<i32>, Box<dyn std::error::Error>> {
-    let re = Regex::new(r"(\d|one|two|three|four|five|six|seven|eight|nine)")?;
+fn parse_digits_regex(input: &str) -> Vec<i32> {
+    let re = Regex::new(r"(\d|one|two|three|four|five|six|seven|eight|nine)").unwrap();
 
     let number_mapping = HashMap::from([
         ("one", '1'),
@@ -42,12 +63,11 @@ fn parse_digits_regex(input: &str) -> Result<Vec<i32>, Box<dyn std::error::Error
     for row in input.lines() {
         let m = re.find(row).unwrap();
         let digit_s: &str = m.as_str();
-        let first: char;
-        if digit_s.len() > 1 {
-            first = *number_mapping.get(digit_s).unwrap();
+        let first: char = if digit_s.len() > 1 {
+            *number_mapping.get(digit_s).unwrap()
         } else {
-            first = digit_s.chars().last().unwrap();
-        }
+            digit_s.chars().last().unwrap()
+        };
 
         let mut last: char = 'a';
         for i in (0..row.len()).rev() {
@@ -61,29 +81,30 @@ fn parse_digits_regex(input: &str) -> Result<Vec<i32>, Box<dyn std::error::Error
                 break;
             }
         }
-        let d: i32 = format!("{}{}", first, last).parse()?;
-        digits.push(d);
+        digits.push(two_digits(first, last));
     }
-    Ok(digits)
+    digits
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let args: Vec<String> = env::args().collect();
-    set_logging_level(&args);
-    let mut input = read_input(&args);
+/// Number written with the given digits, e.g. 38 for '3' and '8'
+fn two_digits(first: char, last: char) -> i32 {
+    format!("{}{}", first, last).parse().unwrap()
+}
 
-    let digits_pt1 = parse_digits(&input)?;
-    debug!("{:?}", digits_pt1);
-    let part_1_sum: i32 = digits_pt1.iter().sum();
-    println!("Part 1: {:?}", part_1_sum);
+fn main() -> Result<(), Box<dyn Error>> {
+    utils::run::<Day01>()
+}
 
-    if args.contains(&"--test".to_string()) {
-        input = fs::read_to_string("test_pt2.txt")?;
-    }
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let digits_pt2 = parse_digits_regex(&input)?;
-    let part_2_sum: i32 = digits_pt2.iter().sum();
-    println!("Part 2: {:?}", part_2_sum);
+    const EXAMPLE: &str = include_str!("../test.txt");
+    const EXAMPLE_PT2: &str = include_str!("../test_pt2.txt");
 
-    Ok(())
+    #[test]
+    fn test_example() {
+        assert_eq!(Day01::part_1(&Day01::parse(EXAMPLE).unwrap()), 142);
+        assert_eq!(Day01::part_2(&Day01::parse(EXAMPLE_PT2).unwrap()), 281);
+    }
 }
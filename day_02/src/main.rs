@@ -1,50 +1,80 @@
-use std::env;
 use std::error::Error;
 
 use log::debug;
 use regex::Regex;
 
-use utils::{read_input, set_logging_level};
+use utils::Solution;
 
 const LIMIT_RED: u32 = 12;
 const LIMIT_GREEN: u32 = 13;
 const LIMIT_BLUE: u32 = 14;
 
-fn parse_valid_games(input: &str) -> Result<Vec<u32>, Box<dyn Error>> {
+struct Day02;
+
+impl Solution for Day02 {
+    type Parsed = Vec<Game>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Box<dyn Error>> {
+        parse_games(input)
+    }
+
+    fn part_1(games: &Self::Parsed) -> u32 {
+        valid_games(games).iter().sum()
+    }
+
+    fn part_2(games: &Self::Parsed) -> u32 {
+        game_powers(games).iter().sum()
+    }
+}
+
+/// Id of a game and the most cubes of each color shown at once
+type Game = (u32, Rgb);
+
+fn parse_games(input: &str) -> Result<Vec<Game>, Box<dyn Error>> {
     let re = Regex::new(r"Game ([\d]+):")?;
 
-    let mut valid_games: Vec<u32> = vec![];
+    let mut games: Vec<Game> = vec![];
     for row in input.lines() {
         let m = re.captures(row).unwrap().get(1).unwrap();
         let game_id: u32 = m.as_str().parse()?;
         debug!("Game id: {:?}", game_id);
-        let (red, green, blue) = count_cubes(&row)?;
+        let cubes = count_cubes(row)?;
+        games.push((game_id, cubes));
+    }
+
+    Ok(games)
+}
+
+fn valid_games(games: &[Game]) -> Vec<u32> {
+    let mut valid_games: Vec<u32> = vec![];
+    for &(game_id, (red, green, blue)) in games {
         debug!("R: {:>2}, G: {:>2}, B: {:>2}", red, green, blue);
 
         if red <= LIMIT_RED && green <= LIMIT_GREEN && blue <= LIMIT_BLUE {
             valid_games.push(game_id);
         }
     }
-
-    Ok(valid_games)
+    valid_games
 }
 
 #[derive(Debug, Clone)]
-struct RGBError {
+struct RgbError {
     color: String,
 }
 
-impl std::fmt::Display for RGBError {
+impl std::fmt::Display for RgbError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Unknown color: {0}", self.color)
     }
 }
 
-impl Error for RGBError {}
+impl Error for RgbError {}
 
-type RGB = (u32, u32, u32);
+type Rgb = (u32, u32, u32);
 
-fn count_cubes(input: &str) -> Result<RGB, Box<dyn Error>> {
+fn count_cubes(input: &str) -> Result<Rgb, Box<dyn Error>> {
     let re = Regex::new(r"([\d]+) (red|green|blue)").unwrap();
 
     let mut red: u32 = 0;
@@ -61,7 +91,7 @@ fn count_cubes(input: &str) -> Result<RGB, Box<dyn Error>> {
             "green" => green = std::cmp::max(green, count),
             "blue" => blue = std::cmp::max(blue, count),
             &_ => {
-                return Err(Box::new(RGBError {
+                return Err(Box::new(RgbError {
                     color: color.to_string(),
                 }))
             }
@@ -71,28 +101,31 @@ fn count_cubes(input: &str) -> Result<RGB, Box<dyn Error>> {
     Ok((red, green, blue))
 }
 
-fn count_game_powers(input: &str) -> Result<Vec<u32>, Box<dyn Error>> {
+fn game_powers(games: &[Game]) -> Vec<u32> {
     let mut game_powers: Vec<u32> = vec![];
 
-    for row in input.lines() {
-        let (red, green, blue) = count_cubes(&row)?;
+    for (_, (red, green, blue)) in games {
         let power = red * green * blue;
         debug!("Power: {}", power);
         game_powers.push(power);
     }
-    Ok(game_powers)
+    game_powers
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    let args: Vec<String> = env::args().collect();
-    set_logging_level(&args);
-    let input = read_input(&args);
-    let valid_games_pt1 = parse_valid_games(&input)?;
-    let sum_pt1: u32 = valid_games_pt1.iter().sum();
-    println!("Part 1: {}", sum_pt1);
-    let game_powers = count_game_powers(&input)?;
-    let sum_pt2: u32 = game_powers.iter().sum();
-    println!("Part 2: {}", sum_pt2);
-
-    Ok(())
+fn main() -> Result<(), Box<dyn Error>> {
+    utils::run::<Day02>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    #[test]
+    fn test_example() {
+        let answers = utils::solve::<Day02>(EXAMPLE).unwrap();
+        assert_eq!(answers.part_1, "8");
+        assert_eq!(answers.part_2, "2286");
+    }
 }
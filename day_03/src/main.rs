@@ -1,18 +1,42 @@
 use std::collections::{HashMap, HashSet};
-use std::env;
 use std::error::Error;
 
 use log::debug;
 use regex::Regex;
 
-use utils::{read_input, set_logging_level};
+use utils::Solution;
 
-fn parse_part_numbers(input: &str) -> Result<Vec<u32>, Box<dyn Error>> {
-    let mut part_numbers: Vec<u32> = vec![];
+struct Day03;
+
+impl Solution for Day03 {
+    type Parsed = Vec<(u32, NumberType)>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Box<dyn Error>> {
+        parse_numbers(input)
+    }
+
+    fn part_1(numbers: &Self::Parsed) -> u32 {
+        let part_numbers_p1 = part_numbers(numbers);
+        debug!("{:?}", part_numbers_p1);
+        part_numbers_p1.iter().sum()
+    }
+
+    fn part_2(numbers: &Self::Parsed) -> u32 {
+        let gear_ratios = gear_ratios(numbers);
+        debug!("{:?}", gear_ratios);
+        gear_ratios.iter().sum()
+    }
+}
+
+/// Numbers of the schematic, with what they are next to
+fn parse_numbers(input: &str) -> Result<Vec<(u32, NumberType)>, Box<dyn Error>> {
+    let mut numbers: Vec<(u32, NumberType)> = vec![];
     let row_len: usize = input.lines().next().unwrap().len();
     debug!("Row lenght: {0}", row_len);
 
-    let continous: String = input.lines().into_iter().collect::<Vec<&str>>().join("");
+    let continous: String = input.lines().collect::<Vec<&str>>().join("");
     let total_len = continous.len();
 
     let re = Regex::new(r"\d+").unwrap();
@@ -21,52 +45,49 @@ fn parse_part_numbers(input: &str) -> Result<Vec<u32>, Box<dyn Error>> {
         let checkpoints = get_checkpoints(m.start(), m.end(), &row_len, &total_len);
         debug!("{:?}", checkpoints);
         let number: u32 = m.as_str().parse()?;
-        match is_part_number(&continous, checkpoints) {
-            NumberType::RANDOM => debug!("{} is not a part", number),
-            NumberType::PART | NumberType::GEAR(_) => {
-                part_numbers.push(number);
+        numbers.push((number, is_part_number(&continous, checkpoints)));
+    }
+
+    Ok(numbers)
+}
+
+fn part_numbers(numbers: &[(u32, NumberType)]) -> Vec<u32> {
+    let mut part_numbers: Vec<u32> = vec![];
+    for (number, number_type) in numbers {
+        match number_type {
+            NumberType::Random => debug!("{} is not a part", number),
+            NumberType::Part | NumberType::Gear(_) => {
+                part_numbers.push(*number);
             }
         }
     }
-
-    Ok(part_numbers)
+    part_numbers
 }
 
-fn parse_gear_ratios(input: &str) -> Result<Vec<u32>, Box<dyn Error>> {
+fn gear_ratios(numbers: &[(u32, NumberType)]) -> Vec<u32> {
     let mut gear_ratios: Vec<u32> = vec![];
 
-    let row_len: usize = input.lines().next().unwrap().len();
-    debug!("Row lenght: {0}", row_len);
-
-    let continous: String = input.lines().into_iter().collect::<Vec<&str>>().join("");
-    let total_len = continous.len();
-
     let mut gear_cache: HashMap<usize, u32> = HashMap::new();
 
-    let re = Regex::new(r"\d+").unwrap();
-    for m in re.find_iter(&continous) {
-        debug!("{:?}", m);
-        let checkpoints = get_checkpoints(m.start(), m.end(), &row_len, &total_len);
-        debug!("{:?}", checkpoints);
-        let number: u32 = m.as_str().parse()?;
-        match is_part_number(&continous, checkpoints) {
-            NumberType::RANDOM => debug!("{} is not a part", number),
-            NumberType::PART => {}
-            NumberType::GEAR(gear_position) => {
-                if let Some(old_part) = gear_cache.remove(&gear_position) {
+    for (number, number_type) in numbers {
+        match number_type {
+            NumberType::Random => debug!("{} is not a part", number),
+            NumberType::Part => {}
+            NumberType::Gear(gear_position) => {
+                if let Some(old_part) = gear_cache.remove(gear_position) {
                     debug!("gear found at position {}", gear_position);
                     debug!("old part {}", old_part);
                     let ratio: u32 = old_part * number;
                     gear_ratios.push(ratio);
                 } else {
-                    gear_cache.insert(gear_position, number);
+                    gear_cache.insert(*gear_position, *number);
                 }
                 debug!("cache {:?}", gear_cache);
             }
         }
     }
 
-    Ok(gear_ratios)
+    gear_ratios
 }
 
 fn get_checkpoints(start: usize, end: usize, row_len: &usize, total_len: &usize) -> HashSet<usize> {
@@ -103,9 +124,9 @@ fn get_checkpoints(start: usize, end: usize, row_len: &usize, total_len: &usize)
 }
 
 enum NumberType {
-    RANDOM,
-    PART,
-    GEAR(usize),
+    Random,
+    Part,
+    Gear(usize),
 }
 
 fn is_part_number(input: &str, checkpoints: HashSet<usize>) -> NumberType {
@@ -113,13 +134,13 @@ fn is_part_number(input: &str, checkpoints: HashSet<usize>) -> NumberType {
         let c = &input[ind..ind + 1].chars().next().unwrap();
 
         if c == &'*' {
-            return NumberType::GEAR(ind);
+            return NumberType::Gear(ind);
         } else if c.is_numeric() || c == &'.' {
             continue;
         }
-        return NumberType::PART;
+        return NumberType::Part;
     }
-    NumberType::RANDOM
+    NumberType::Random
 }
 
 fn make_range<T: std::cmp::PartialOrd>(left: T, right: T) -> Box<std::ops::Range<T>> {
@@ -131,19 +152,19 @@ fn make_range<T: std::cmp::PartialOrd>(left: T, right: T) -> Box<std::ops::Range
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let args: Vec<String> = env::args().collect();
-    set_logging_level(&args);
-    let input = read_input(&args);
+    utils::run::<Day03>()
+}
 
-    let part_numbers_p1 = parse_part_numbers(&input)?;
-    debug!("{:?}", part_numbers_p1);
-    let schematic_number_p1: u32 = part_numbers_p1.iter().sum();
-    println!("Part 1: {}", schematic_number_p1);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let gear_ratios = parse_gear_ratios(&input)?;
-    debug!("{:?}", gear_ratios);
-    let gear_ratio_sum: u32 = gear_ratios.iter().sum();
-    println!("Part 2: {}", gear_ratio_sum);
+    const EXAMPLE: &str = include_str!("../test.txt");
 
-    Ok(())
+    #[test]
+    fn test_example() {
+        let answers = utils::solve::<Day03>(EXAMPLE).unwrap();
+        assert_eq!(answers.part_1, "4361");
+        assert_eq!(answers.part_2, "467835");
+    }
 }
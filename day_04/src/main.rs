@@ -1,14 +1,34 @@
 use std::collections::{HashSet, VecDeque};
-use std::env;
 use std::error::Error;
 
 use log::debug;
 use regex::Regex;
 
-use utils::{read_input, set_logging_level};
+use utils::Solution;
 
-fn parse_scores(input: &str) -> Vec<usize> {
-    let mut scores: Vec<usize> = vec![];
+struct Day04;
+
+impl Solution for Day04 {
+    type Parsed = Vec<usize>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Box<dyn Error>> {
+        Ok(parse_matches(input))
+    }
+
+    fn part_1(matches: &Self::Parsed) -> usize {
+        scores(matches).iter().sum()
+    }
+
+    fn part_2(matches: &Self::Parsed) -> usize {
+        count_with_winnings(matches)
+    }
+}
+
+/// Number of winning numbers on each card
+fn parse_matches(input: &str) -> Vec<usize> {
+    let mut matches: Vec<usize> = vec![];
 
     let re = Regex::new(r"\d+").unwrap();
 
@@ -27,12 +47,19 @@ fn parse_scores(input: &str) -> Vec<usize> {
         debug!("Right {:?}", right_numbers);
         let count = right_numbers.intersection(&left_numbers).count();
         debug!("count {}", count);
-        let score: usize;
-        if count > 0 {
-            score = 2_usize.pow((count - 1) as u32);
+        matches.push(count);
+    }
+    matches
+}
+
+fn scores(matches: &[usize]) -> Vec<usize> {
+    let mut scores: Vec<usize> = vec![];
+    for &count in matches {
+        let score: usize = if count > 0 {
+            2_usize.pow((count - 1) as u32)
         } else {
-            score = 0;
-        }
+            0
+        };
         debug!("score {}", score);
         scores.push(score);
     }
@@ -45,35 +72,18 @@ struct Card {
     copies: usize,
 }
 
-fn parse_with_winnings(input: &str) -> usize {
-    let mut card_stack: VecDeque<Card> = VecDeque::new();
-
-    let re = Regex::new(r"\d+").unwrap();
-
-    for row in input.lines() {
-        let (_, stripped) = row.split_once(":").unwrap();
-        let (left_str, right_str) = stripped.split_once("|").unwrap();
-        let left_numbers: HashSet<u32> = re
-            .find_iter(left_str)
-            .map(|m| m.as_str().parse().unwrap())
-            .collect();
-        debug!("Left {:?}", left_numbers);
-        let right_numbers: HashSet<u32> = re
-            .find_iter(right_str)
-            .map(|m| m.as_str().parse().unwrap())
-            .collect();
-        debug!("Right {:?}", right_numbers);
-        let points = right_numbers.intersection(&left_numbers).count();
-        debug!("points {}", points);
-        card_stack.push_back(Card { points, copies: 1 })
-    }
+fn count_with_winnings(matches: &[usize]) -> usize {
+    let mut card_stack: VecDeque<Card> = matches
+        .iter()
+        .map(|&points| Card { points, copies: 1 })
+        .collect();
     debug!("initial card stack \n{:?}", card_stack);
 
     let mut processed_cards: Vec<Card> = Vec::new();
     while let Some(card) = card_stack.pop_front() {
         debug!("{:?}", card);
-        for i in 0..card.points {
-            card_stack[i].copies += card.copies;
+        for next_card in card_stack.iter_mut().take(card.points) {
+            next_card.copies += card.copies;
         }
         processed_cards.push(card);
     }
@@ -83,15 +93,19 @@ fn parse_with_winnings(input: &str) -> usize {
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let args: Vec<String> = env::args().collect();
-    set_logging_level(&args);
-    let input = read_input(&args);
+    utils::run::<Day04>()
+}
 
-    let scores_p1 = parse_scores(&input);
-    let sum_p1: usize = scores_p1.iter().sum();
-    println!("Part 1: {}", sum_p1);
-    let total_cards = parse_with_winnings(&input);
-    println!("Part 2: {}", total_cards);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    Ok(())
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    #[test]
+    fn test_example() {
+        let answers = utils::solve::<Day04>(EXAMPLE).unwrap();
+        assert_eq!(answers.part_1, "13");
+        assert_eq!(answers.part_2, "30");
+    }
 }
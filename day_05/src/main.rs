@@ -1,12 +1,35 @@
-use std::env;
 use std::error::Error;
 use std::ops::Range;
-use std::time::SystemTime;
 
 use log::debug;
 use regex::Regex;
 
-use utils::{read_input, set_logging_level};
+use utils::{blocks, Solution};
+
+struct Day05;
+
+impl Solution for Day05 {
+    type Parsed = (Vec<u32>, Mappings);
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Box<dyn Error>> {
+        let (seeds, mappings) = parse_input(input)?;
+        debug!("Seeds: {:?}", seeds);
+        debug!("Mappings {:?}", mappings);
+        Ok((seeds, mappings))
+    }
+
+    fn part_1((seeds, mappings): &Self::Parsed) -> u32 {
+        let locations = part_1(mappings, seeds);
+        debug!("Locations: {:?}", locations);
+        *locations.iter().min().unwrap()
+    }
+
+    fn part_2((seeds, mappings): &Self::Parsed) -> u32 {
+        part_2(mappings, seeds)
+    }
+}
 
 #[derive(Debug)]
 struct Path {
@@ -41,7 +64,7 @@ impl Destination<Map> for Map {
                 return destination;
             }
         }
-        return value;
+        value
     }
 }
 
@@ -51,9 +74,9 @@ fn walk_index(mappings: &Mappings, index: usize, value: u32) -> u32 {
     let destination = mappings[index].to_destination(value);
     let new_index = index + 1;
     if new_index == mappings.len() {
-        return destination;
+        destination
     } else {
-        return walk_index(mappings, new_index, destination);
+        walk_index(mappings, new_index, destination)
     }
 }
 
@@ -62,21 +85,17 @@ fn part_1(mappings: &Mappings, seeds: &[u32]) -> Vec<u32> {
         .iter()
         .map(|seed| walk_index(mappings, 0, *seed))
         .collect();
-    return locations;
+    locations
 }
 
-fn part_2(mappings: Mappings, seeds: &[u32]) -> u32 {
+fn part_2(mappings: &Mappings, seeds: &[u32]) -> u32 {
     let seed_ranges = make_seed_ranges(seeds);
 
-    let start = SystemTime::now();
     let mut min_location: u32 = u32::MAX;
     for seed in seed_ranges.iter().flat_map(|it| it.clone()) {
-        min_location = min_location.min(walk_index(&mappings, 0, seed))
+        min_location = min_location.min(walk_index(mappings, 0, seed))
     }
-
-    let duration = SystemTime::now().duration_since(start).unwrap();
-    println!("That took: {:?}", duration);
-    return min_location;
+    min_location
 }
 
 fn make_seed_ranges(seeds: &[u32]) -> Vec<Range<u32>> {
@@ -88,12 +107,13 @@ fn make_seed_ranges(seeds: &[u32]) -> Vec<Range<u32>> {
         id_1 += 2;
         id_2 += 2;
     }
-    return seed_ranges;
+    seed_ranges
 }
 
 fn parse_input(input: &str) -> Result<(Vec<u32>, Mappings), Box<dyn Error>> {
     let re_seeds: Regex = Regex::new(r"\d+")?;
-    let (seed_str, map_str) = input.split_once("seed-to-soil map:").unwrap();
+    let mut blocks = blocks(input);
+    let seed_str = blocks.next().ok_or("No seeds")?;
 
     let seeds: Vec<u32> = re_seeds
         .find_iter(seed_str)
@@ -101,10 +121,9 @@ fn parse_input(input: &str) -> Result<(Vec<u32>, Mappings), Box<dyn Error>> {
         .collect();
 
     let re_map: Regex = Regex::new(r"(\d+) (\d+) (\d+)")?;
-    debug!("{map_str}");
-
     let mut mappings_v: Vec<Vec<Path>> = vec![];
-    for block in map_str.split(":") {
+    for block in blocks {
+        debug!("{block}");
         let mut block_maps: Vec<Path> = vec![];
         for line in block.lines() {
             debug!("line: {}", line);
@@ -129,29 +148,22 @@ fn parse_input(input: &str) -> Result<(Vec<u32>, Mappings), Box<dyn Error>> {
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let args: Vec<String> = env::args().collect();
-    set_logging_level(&args);
-    let input = read_input(&args);
-
-    let (seeds, mappings) = parse_input(&input)?;
-    debug!("Seeds: {:?}", seeds);
-    debug!("Mappings {:?}", mappings);
-
-    let locations = part_1(&mappings, &seeds);
-    debug!("Locations: {:?}", locations);
-    let min_location_p1 = locations.iter().min().unwrap();
-    println!("Part 1: {}", min_location_p1);
-
-    let min_location_p2 = part_2(mappings, &seeds);
-    println!("Part 2: {}", min_location_p2);
-
-    Ok(())
+    utils::run::<Day05>()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    #[test]
+    fn test_example() {
+        let answers = utils::solve::<Day05>(EXAMPLE).unwrap();
+        assert_eq!(answers.part_1, "35");
+        assert_eq!(answers.part_2, "46");
+    }
+
     #[test]
     fn test_path_contains() {
         let path = Path {
@@ -1,11 +1,30 @@
-use std::env;
 use std::error::Error;
 
 use itertools::izip;
 use log::debug;
 use regex::Regex;
 
-use utils::{read_input, set_logging_level};
+use utils::Solution;
+
+struct Day06;
+
+impl Solution for Day06 {
+    type Parsed = Vec<RaceRecord>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Box<dyn Error>> {
+        Ok(parse_inputs(input))
+    }
+
+    fn part_1(records: &Self::Parsed) -> u64 {
+        part_1(records)
+    }
+
+    fn part_2(records: &Self::Parsed) -> u64 {
+        part_2(records)
+    }
+}
 
 #[derive(Debug, Clone, Copy)]
 struct RaceRecord {
@@ -18,7 +37,7 @@ impl RaceRecord {
         let low = (self.time - (self.time.powf(2.0) - 4.0 * self.distance).sqrt()) / 2.0;
         let high = (self.time + (self.time.powf(2.0) - 4.0 * self.distance).sqrt()) / 2.0;
         debug!("low: {}, high: {}", low, high);
-        return ((low + 1.0).floor(), (high - 1.0).ceil());
+        ((low + 1.0).floor(), (high - 1.0).ceil())
     }
 
     fn calc_ways(&self) -> u64 {
@@ -40,10 +59,7 @@ impl RaceRecord {
 }
 
 fn part_1(records: &[RaceRecord]) -> u64 {
-    records
-        .iter()
-        .map(|race| race.calc_ways())
-        .fold(1, |acc, elem| acc * elem)
+    records.iter().map(|race| race.calc_ways()).product()
 }
 
 fn part_2(records: &[RaceRecord]) -> u64 {
@@ -67,29 +83,26 @@ fn parse_inputs(input: &str) -> Vec<RaceRecord> {
         })
         .collect();
     debug!("Races: {:?}", races);
-    return races;
+    races
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let args: Vec<String> = env::args().collect();
-    set_logging_level(&args);
-    let input = read_input(&args);
-
-    let race_records = parse_inputs(&input);
-    let part_1 = part_1(&race_records);
-    println!("Part 1: {}", part_1);
-
-    log::set_max_level(log::LevelFilter::Debug);
-    let part_2 = part_2(&race_records);
-    println!("Part 2: {}", part_2);
-
-    Ok(())
+    utils::run::<Day06>()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    #[test]
+    fn test_example() {
+        let answers = utils::solve::<Day06>(EXAMPLE).unwrap();
+        assert_eq!(answers.part_1, "288");
+        assert_eq!(answers.part_2, "71503");
+    }
+
     #[test]
     fn test_race_record_calc_edges() {
         let race = RaceRecord {
@@ -1,11 +1,10 @@
 use std::collections::HashMap;
-use std::env;
 use std::error::Error;
 
 use log::debug;
 use phf::phf_map;
 
-use utils::{read_input, set_logging_level};
+use utils::Solution;
 
 static CARD_STREGTH: phf::Map<char, u32> = phf_map! {
     '2' => 1,
@@ -23,7 +22,7 @@ static CARD_STREGTH: phf::Map<char, u32> = phf_map! {
     'A' => 13,
 };
 
-#[derive(Debug, PartialEq, PartialOrd)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
 enum HandType {
     HighCard = 0,
     OnePair = 1,
@@ -77,13 +76,35 @@ impl FromCards for HandType {
             return Self::HighCard;
         }
         // FullHouse
-        return Self::FullHouse;
+        Self::FullHouse
+    }
+}
+
+struct Day07;
+
+impl Solution for Day07 {
+    type Parsed = Vec<Hand>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Box<dyn Error>> {
+        let hands = parse_hands(input);
+        debug!("Hands: {:?}", hands);
+        Ok(hands)
+    }
+
+    fn part_1(hands: &Self::Parsed) -> u64 {
+        part_1(hands)
+    }
+
+    fn part_2(hands: &Self::Parsed) -> u64 {
+        part_2(hands)
     }
 }
 
 #[derive(Debug)]
-struct Hand<'a> {
-    cards: &'a str,
+struct Hand {
+    cards: String,
     bid: u64,
 }
 
@@ -100,7 +121,7 @@ fn parse_hands(input: &str) -> Vec<Hand> {
         .map(|line| {
             let (cards, bid) = line.split_once(" ").unwrap();
             Hand {
-                cards: cards,
+                cards: cards.to_string(),
                 bid: bid.parse().unwrap(),
             }
         })
@@ -117,14 +138,14 @@ struct TypedHand {
 impl TypedHand {
     fn from_hand(hand: &Hand) -> Self {
         Self {
-            hand_type: HandType::from_cards(hand.cards),
-            powers: Powers::from_cards(hand.cards),
+            hand_type: HandType::from_cards(&hand.cards),
+            powers: Powers::from_cards(&hand.cards),
             bid: hand.bid,
         }
     }
 
     fn from_hand_with_jokers(hand: &Hand) -> Self {
-        let count = count_cards(hand.cards);
+        let count = count_cards(&hand.cards);
         let jokers = match count.get(&'J') {
             Some(val) => val,
             None => {
@@ -154,7 +175,7 @@ impl TypedHand {
             target_card_strenth = strength;
         }
         let new_cards = hand.cards.replace("J", &target_card.to_string());
-        let powers: Powers = Powers::from_cards(hand.cards)
+        let powers: Powers = Powers::from_cards(&hand.cards)
             .into_iter()
             .map(|p| if p == 10 { 0 } else { p })
             .collect::<Vec<u32>>()
@@ -166,7 +187,7 @@ impl TypedHand {
         debug!("Powers: {:?}", powers);
         let ret = Self {
             hand_type: HandType::from_cards(&new_cards),
-            powers: powers,
+            powers,
             bid: hand.bid,
         };
         debug!("Hand type: {:?}", ret.hand_type);
@@ -176,21 +197,17 @@ impl TypedHand {
 
 impl PartialOrd for TypedHand {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        match self.hand_type.partial_cmp(&other.hand_type) {
-            Some(core::cmp::Ordering::Equal) => {}
-            ord => return ord,
-        }
-        match self.powers.partial_cmp(&other.powers) {
-            Some(core::cmp::Ordering::Equal) => {}
-            ord => return ord,
-        }
-        Some(core::cmp::Ordering::Equal)
+        Some(self.cmp(other))
     }
 }
 
 impl Ord for TypedHand {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.partial_cmp(other).unwrap()
+        match self.hand_type.cmp(&other.hand_type) {
+            core::cmp::Ordering::Equal => {}
+            ord => return ord,
+        }
+        self.powers.cmp(&other.powers)
     }
 }
 
@@ -203,20 +220,17 @@ impl PartialEq for TypedHand {
 impl Eq for TypedHand {}
 
 fn part_1(hands: &[Hand]) -> u64 {
-    let typed_hands: Vec<TypedHand> = hands.iter().map(|h| TypedHand::from_hand(h)).collect();
+    let typed_hands: Vec<TypedHand> = hands.iter().map(TypedHand::from_hand).collect();
     debug!("Typed hands: {:?}", typed_hands);
-    let mut sorted_hands: Vec<&TypedHand> = typed_hands.iter().map(|h| h).collect();
+    let mut sorted_hands: Vec<&TypedHand> = typed_hands.iter().collect();
     sorted_hands.sort();
     count_winnings(&sorted_hands)
 }
 
 fn part_2(hands: &[Hand]) -> u64 {
-    let typed_hands: Vec<TypedHand> = hands
-        .iter()
-        .map(|h| TypedHand::from_hand_with_jokers(h))
-        .collect();
+    let typed_hands: Vec<TypedHand> = hands.iter().map(TypedHand::from_hand_with_jokers).collect();
     debug!("Typed hands: {:?}", typed_hands);
-    let mut sorted_hands: Vec<&TypedHand> = typed_hands.iter().map(|h| h).collect();
+    let mut sorted_hands: Vec<&TypedHand> = typed_hands.iter().collect();
     sorted_hands.sort();
     count_winnings(&sorted_hands)
 }
@@ -230,26 +244,22 @@ fn count_winnings(sorted_hands: &[&TypedHand]) -> u64 {
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let args: Vec<String> = env::args().collect();
-    set_logging_level(&args);
-    let input = read_input(&args);
-
-    let hands = parse_hands(&input);
-    debug!("Hands: {:?}", hands);
-
-    let winnigs = part_1(&hands);
-    println!("Part 1: {}", winnigs);
-
-    let winnigs_p2 = part_2(&hands);
-    println!("Part 2: {}", winnigs_p2);
-
-    Ok(())
+    utils::run::<Day07>()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    #[test]
+    fn test_example() {
+        let answers = utils::solve::<Day07>(EXAMPLE).unwrap();
+        assert_eq!(answers.part_1, "6440");
+        assert_eq!(answers.part_2, "5905");
+    }
+
     #[test]
     fn compare_hand_type() {
         assert!(HandType::Fives > HandType::Fours);
@@ -1,31 +1,54 @@
+use std::collections::HashMap;
 use std::error::Error;
-use std::fs;
-use std::{collections::HashMap, env};
 
 use log::debug;
 use regex::Regex;
 
-use utils::{read_input, set_logging_level};
+use utils::{blocks, Solution};
+
+struct Day08;
+
+impl Solution for Day08 {
+    type Parsed = (Vec<Side>, Nodes);
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Box<dyn Error>> {
+        Ok(parse_input(input))
+    }
+
+    fn part_1((steps, nodes): &Self::Parsed) -> usize {
+        count_steps(steps, nodes, "AAA", "ZZZ").unwrap()
+    }
+
+    fn part_2((steps, nodes): &Self::Parsed) -> usize {
+        count_steps_simul(steps, nodes)
+    }
+}
+
+/// Left and right nodes of each node
+type Nodes = HashMap<String, (String, String)>;
 
 #[derive(Debug)]
 enum Side {
-    LEFT,
-    RIGHT,
+    Left,
+    Right,
 }
 
 impl Side {
     fn from_char(c: &char) -> Option<Self> {
         debug!("Char: {}", c);
-        match c {
-            &'L' => Some(Self::LEFT),
-            &'R' => Some(Self::RIGHT),
+        match *c {
+            'L' => Some(Self::Left),
+            'R' => Some(Self::Right),
             _ => None,
         }
     }
 }
 
-fn parse_input(input: &str) -> (Vec<Side>, HashMap<&str, (&str, &str)>) {
-    let (steps_str, node_str) = input.split_once("\r\n\r\n").unwrap();
+fn parse_input(input: &str) -> (Vec<Side>, Nodes) {
+    let mut blocks = blocks(input);
+    let (steps_str, node_str) = (blocks.next().unwrap(), blocks.next().unwrap());
     debug!("Steps str: {}", steps_str);
     let steps: Vec<Side> = steps_str
         .chars()
@@ -34,31 +57,21 @@ fn parse_input(input: &str) -> (Vec<Side>, HashMap<&str, (&str, &str)>) {
     debug!("Steps: {:?}", steps);
 
     let re = Regex::new(r"(\w\w\w) = \((\w\w\w), (\w\w\w)\)").unwrap();
-    let nodes: HashMap<&str, (&str, &str)> = re
+    let nodes: Nodes = re
         .captures_iter(node_str)
-        .map(|m| {
-            (
-                m.get(1).unwrap().as_str(),
-                (m.get(2).unwrap().as_str(), m.get(3).unwrap().as_str()),
-            )
-        })
+        .map(|m| (m[1].to_string(), (m[2].to_string(), m[3].to_string())))
         .collect();
     debug!("Nodes: {:?}", nodes);
     (steps, nodes)
 }
 
-fn count_steps(
-    steps: &[Side],
-    nodes: &HashMap<&str, (&str, &str)>,
-    start: &str,
-    end: &str,
-) -> Option<usize> {
+fn count_steps(steps: &[Side], nodes: &Nodes, start: &str, end: &str) -> Option<usize> {
     let mut destination: &str = start;
     for (i, step) in steps.iter().cycle().enumerate() {
         let options = nodes.get(destination).unwrap();
         destination = match step {
-            Side::LEFT => options.0,
-            Side::RIGHT => options.1,
+            Side::Left => &options.0,
+            Side::Right => &options.1,
         };
         if destination.ends_with(end) {
             return Some(i + 1);
@@ -67,20 +80,20 @@ fn count_steps(
     None
 }
 
-fn count_steps_simul(steps: &[Side], nodes: &HashMap<&str, (&str, &str)>) -> usize {
+fn count_steps_simul(steps: &[Side], nodes: &Nodes) -> usize {
     let destinations: Vec<&str> = nodes
         .keys()
         .filter(|n| n.ends_with("A"))
-        .map(|n| *n)
+        .map(String::as_str)
         .collect();
     debug!("Initial destinations: {:?}", destinations);
     let first_goals: Vec<usize> = destinations
         .iter()
-        .map(|start| count_steps(steps, nodes, start, &"Z").unwrap())
+        .map(|start| count_steps(steps, nodes, start, "Z").unwrap())
         .collect();
     debug!("First goals: {:?}", first_goals);
 
-    return lcm(&first_goals).unwrap();
+    lcm(&first_goals).unwrap()
 }
 
 // https://en.wikipedia.org/wiki/Least_common_multiple#Using_the_greatest_common_divisor
@@ -93,7 +106,7 @@ fn lcm(nums: &[usize]) -> Option<usize> {
         return Some(a);
     }
     let b = lcm(&nums[1..]).unwrap();
-    return Some((a * b) / gcd(a, b));
+    Some((a * b) / gcd(a, b))
 }
 
 // https://en.wikipedia.org/wiki/Greatest_common_divisor#Euclidean_algorithm
@@ -105,22 +118,30 @@ fn gcd(a: usize, b: usize) -> usize {
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let args: Vec<String> = env::args().collect();
-    set_logging_level(&args);
-    let mut input = read_input(&args);
+    utils::run::<Day08>()
+}
 
-    let (mut steps, mut nodes) = parse_input(&input);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let steps_p1 = count_steps(&steps, &nodes, &"AAA", &"ZZZ").unwrap();
-    println!("Part 1: {}", steps_p1);
+    const EXAMPLE: &str = include_str!("../test.txt");
+    const EXAMPLE_PT2: &str = include_str!("../test_pt2.txt");
 
-    if args.contains(&"--test".to_string()) {
-        input = fs::read_to_string("test_pt2.txt")?;
-        (steps, nodes) = parse_input(&input);
+    #[test]
+    fn test_example() {
+        assert_eq!(Day08::part_1(&Day08::parse(EXAMPLE).unwrap()), 2);
+        assert_eq!(Day08::part_2(&Day08::parse(EXAMPLE_PT2).unwrap()), 6);
     }
 
-    let steps_p2 = count_steps_simul(&steps, &nodes);
-    println!("Part 2: {}", steps_p2);
-
-    Ok(())
+    #[test]
+    fn test_parse_input_line_endings() {
+        let lf = "LR\n\nAAA = (BBB, ZZZ)\nBBB = (AAA, ZZZ)\nZZZ = (ZZZ, ZZZ)\n";
+        for input in [lf.to_string(), lf.replace('\n', "\r\n")] {
+            let (steps, nodes) = parse_input(&input);
+            assert_eq!(steps.len(), 2);
+            assert_eq!(nodes.len(), 3);
+            assert_eq!(count_steps(&steps, &nodes, "AAA", "ZZZ"), Some(2));
+        }
+    }
 }
@@ -1,19 +1,34 @@
-use std::env;
 use std::error::Error;
 
 use log::debug;
 
-use utils::{read_input, set_logging_level};
+use utils::Solution;
 
-fn parse_input(input: &str) -> Vec<Vec<i64>> {
-    input
+struct Day09;
+
+impl Solution for Day09 {
+    type Parsed = Vec<Vec<i64>>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Box<dyn Error>> {
+        parse_input(input)
+    }
+
+    fn part_1(histories: &Self::Parsed) -> i64 {
+        part_1(histories)
+    }
+
+    fn part_2(histories: &Self::Parsed) -> i64 {
+        part_2(histories)
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<Vec<i64>>, Box<dyn Error>> {
+    Ok(input
         .lines()
-        .map(|line| {
-            line.split(' ')
-                .map(|ns| ns.parse::<i64>().unwrap())
-                .collect()
-        })
-        .collect()
+        .map(|line| line.split(' ').map(|ns| ns.parse::<i64>()).collect())
+        .collect::<Result<_, _>>()?)
 }
 
 fn walk_history(history: &[i64], move_forwards: bool) -> i64 {
@@ -48,31 +63,33 @@ fn walk_history(history: &[i64], move_forwards: bool) -> i64 {
 }
 
 fn part_1(histories: &[Vec<i64>]) -> i64 {
-    return histories
+    histories
         .iter()
         .map(|hist| walk_history(&hist[..], true))
-        .sum();
+        .sum()
 }
 
 fn part_2(histories: &[Vec<i64>]) -> i64 {
-    return histories
+    histories
         .iter()
         .map(|hist| walk_history(&hist[..], false))
-        .sum();
+        .sum()
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let args: Vec<String> = env::args().collect();
-    set_logging_level(&args);
-    let input = read_input(&args);
-
-    let histories = parse_input(&input);
+    utils::run::<Day09>()
+}
 
-    let explorations = part_1(&histories);
-    println!("Part 1: {}", explorations);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let explorations_p2 = part_2(&histories);
-    println!("Part 2: {}", explorations_p2);
+    const EXAMPLE: &str = "0 3 6 9 12 15\n1 3 6 10 15 21\n10 13 16 21 30 45\n";
 
-    Ok(())
+    #[test]
+    fn test_example() {
+        let answers = utils::solve::<Day09>(EXAMPLE).unwrap();
+        assert_eq!(answers.part_1, "114");
+        assert_eq!(answers.part_2, "2");
+    }
 }
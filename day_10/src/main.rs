@@ -1,12 +1,34 @@
 use std::collections::{HashMap, HashSet};
-use std::env;
 use std::error::Error;
 
 use log::{debug, info};
 
-use utils::{read_input, set_logging_level};
+use utils::Solution;
 
-#[derive(Debug, PartialEq)]
+struct Day10;
+
+impl Solution for Day10 {
+    /// Part 2 needs the loop walked in part 1, so it's walked once with the parsing
+    type Parsed = (Point, PipeMap, HashSet<Point>);
+    type Answer1 = usize;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Box<dyn Error>> {
+        let (start_point, map) = parse_input(input);
+        let walked_paths = part_1(&map, &start_point);
+        Ok((start_point, map, walked_paths))
+    }
+
+    fn part_1((_, _, walked_paths): &Self::Parsed) -> usize {
+        walked_paths.len() / 2
+    }
+
+    fn part_2((start_point, map, walked_paths): &Self::Parsed) -> u64 {
+        part_2(map.clone(), start_point, walked_paths)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
 enum PipeTile {
     Vertical,
     Horizontal,
@@ -108,10 +130,7 @@ struct Path {
 
 impl Path {
     fn move_next(&self, pipe: &PipeTile) -> Option<Self> {
-        let next_point = match pipe.next_point(&self.from, &self.current) {
-            Some(p) => p,
-            None => return None,
-        };
+        let next_point = pipe.next_point(&self.from, &self.current)?;
         Some(Self {
             from: self.current,
             current: next_point,
@@ -198,7 +217,7 @@ fn part_2(mut map: PipeMap, start_point: &Point, walked_paths: &HashSet<Point>)
         }
     }
 
-    return points_inside;
+    points_inside
 }
 
 fn get_start_tile_type(start_point: &Point, point_1: &Point, point_2: &Point) -> PipeTile {
@@ -311,25 +330,22 @@ fn starting_paths(map: &PipeMap, start_point: &Point) -> Vec<Path> {
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
-    let args: Vec<String> = env::args().collect();
-    set_logging_level(&args);
-    let input = read_input(&args);
-    let (start_point, map) = parse_input(&input);
-
-    let walked_paths = part_1(&map, &start_point);
-    let steps_p1 = walked_paths.len() / 2;
-    println!("Part 1: {}", steps_p1);
-
-    let points_within = part_2(map, &start_point, &walked_paths);
-    println!("Part 2: {}", points_within);
-
-    Ok(())
+    utils::run::<Day10>()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const EXAMPLE: &str = include_str!("../test.txt");
+
+    #[test]
+    fn test_example() {
+        let answers = utils::solve::<Day10>(EXAMPLE).unwrap();
+        assert_eq!(answers.part_1, "4");
+        assert_eq!(answers.part_2, "1");
+    }
+
     #[test]
     fn test_pipe_tile_south_to_west() {
         let current: Point = (1, 1);
@@ -0,0 +1,243 @@
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use super::run::{binary, build_day};
+use super::workspace::{day_name, find_root};
+use crate::error::{AocError, Result};
+
+/// History of benchmark runs, at the root of the workspace
+pub const BENCH_FILE: &str = "benchmarks.json";
+
+/// One `aocrs bench` invocation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchRun {
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+    /// Commit the solutions were built from, if in a git repository
+    pub commit: Option<String>,
+    /// Times each solution was run
+    pub runs: usize,
+    pub results: Vec<PartTiming>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PartTiming {
+    pub day: u32,
+    pub part: u8,
+    pub min_ms: f64,
+    pub median_ms: f64,
+    pub mean_ms: f64,
+}
+
+/// Run the release builds of the days repeatedly on their personal inputs
+///
+/// A part is timed until its answer is printed, starting from the answer of the previous
+/// part, so the time of part 1 includes starting the process and parsing the input.
+pub fn bench(days: &[u32], runs: usize) -> Result<()> {
+    if runs == 0 {
+        return Err("Benchmarks need at least one run".into());
+    }
+    let path = find_root().unwrap_or_default().join(BENCH_FILE);
+    let mut history = load(&path)?;
+    let previous = history.last().cloned();
+
+    let mut results = vec![];
+    for day in days {
+        build_day(*day, true)?;
+        let mut samples: [Vec<Duration>; 2] = [vec![], vec![]];
+        for _ in 0..runs {
+            let parts =
+                time_parts(binary(*day, true)?).map_err(|e| format!("{}: {}", day_name(day), e))?;
+            for (samples, time) in samples.iter_mut().zip(parts) {
+                samples.extend(time);
+            }
+        }
+        for (part, samples) in (1..=2).zip(samples) {
+            if let Some(timing) = timing(*day, part, &samples) {
+                results.push(timing);
+            }
+        }
+    }
+
+    let run = BenchRun {
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+        commit: commit(),
+        runs,
+        results,
+    };
+    print!("{}", render(&run, previous.as_ref()));
+    history.push(run);
+    fs::write(&path, serde_json::to_string_pretty(&history)?)?;
+    println!("Results added to {}", path.display());
+    Ok(())
+}
+
+fn load(path: &Path) -> Result<Vec<BenchRun>> {
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    serde_json::from_str(&fs::read_to_string(path)?)
+        .map_err(|e| AocError::Parse(format!("{}: {}", path.display(), e)))
+}
+
+fn commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Time from the start of the solution to each of its answers
+fn time_parts(mut command: Command) -> Result<[Option<Duration>; 2]> {
+    let start = Instant::now();
+    let mut child = command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+    let mut times = [None, None];
+    let mut previous = start;
+    if let Some(stdout) = child.stdout.take() {
+        for line in BufReader::new(stdout).lines() {
+            let line = line?;
+            let part = if line.starts_with("Part 1:") {
+                0
+            } else if line.starts_with("Part 2:") {
+                1
+            } else {
+                continue;
+            };
+            let now = Instant::now();
+            times[part] = Some(now - previous);
+            previous = now;
+        }
+    }
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(format!("failed: {}", status).into());
+    }
+    Ok(times)
+}
+
+fn timing(day: u32, part: u8, samples: &[Duration]) -> Option<PartTiming> {
+    let (min, median, mean) = stats(samples)?;
+    Some(PartTiming {
+        day,
+        part,
+        min_ms: millis(min),
+        median_ms: millis(median),
+        mean_ms: millis(mean),
+    })
+}
+
+/// Minimum, median and mean of the samples
+pub fn stats(samples: &[Duration]) -> Option<(Duration, Duration, Duration)> {
+    let mut sorted = samples.to_vec();
+    sorted.sort();
+    let min = *sorted.first()?;
+    let middle = sorted.len() / 2;
+    let median = match sorted.len() % 2 {
+        0 => (sorted[middle - 1] + sorted[middle]) / 2,
+        _ => sorted[middle],
+    };
+    let mean = sorted.iter().sum::<Duration>() / sorted.len() as u32;
+    Some((min, median, mean))
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Table of the timings, with the change of the median since the previous run
+pub fn render(run: &BenchRun, previous: Option<&BenchRun>) -> String {
+    let mut out = format!(
+        "Day  Part  {:>10} {:>10} {:>10}  {:>8}\n",
+        "Min ms", "Median ms", "Mean ms", "Change"
+    );
+    for timing in &run.results {
+        let change = previous
+            .and_then(|p| {
+                p.results
+                    .iter()
+                    .find(|t| t.day == timing.day && t.part == timing.part)
+            })
+            .filter(|before| before.median_ms > 0.0)
+            .map(|before| {
+                let change = (timing.median_ms / before.median_ms - 1.0) * 100.0;
+                format!("{:+.1}%", change)
+            })
+            .unwrap_or_default();
+        out.push_str(&format!(
+            "{:>3}  {:>4}  {:>10.3} {:>10.3} {:>10.3}  {:>8}\n",
+            timing.day, timing.part, timing.min_ms, timing.median_ms, timing.mean_ms, change
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: &[u64]) -> Vec<Duration> {
+        millis.iter().map(|m| Duration::from_millis(*m)).collect()
+    }
+
+    #[test]
+    fn test_stats() {
+        assert_eq!(
+            stats(&ms(&[5, 1, 3])),
+            Some((
+                Duration::from_millis(1),
+                Duration::from_millis(3),
+                Duration::from_millis(3)
+            ))
+        );
+        assert_eq!(
+            stats(&ms(&[4, 1, 2, 9])),
+            Some((
+                Duration::from_millis(1),
+                Duration::from_millis(3),
+                Duration::from_millis(4)
+            ))
+        );
+        assert_eq!(stats(&[]), None);
+    }
+
+    #[test]
+    fn test_render() {
+        let timing = |part, median_ms| PartTiming {
+            day: 5,
+            part,
+            min_ms: 1.0,
+            median_ms,
+            mean_ms: 2.0,
+        };
+        let run = |results| BenchRun {
+            timestamp: 0,
+            commit: None,
+            runs: 10,
+            results,
+        };
+        let previous = run(vec![timing(1, 2.0)]);
+        let current = run(vec![timing(1, 1.5), timing(2, 4.0)]);
+        assert_eq!(
+            render(&current, Some(&previous)),
+            "Day  Part      Min ms  Median ms    Mean ms    Change\n  \
+             5     1       1.000      1.500      2.000    -25.0%\n  \
+             5     2       1.000      4.000      2.000          \n"
+        );
+    }
+}
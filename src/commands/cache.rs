@@ -0,0 +1,185 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use cacache::Metadata;
+use chrono::{DateTime, Local};
+use http_cache_reqwest::{CACacheManager, CacheManager};
+
+use super::config;
+use super::session::is_logged_in;
+use crate::error::Result;
+
+/// Directory of the HTTP cache, `http-cacache` in the configured cache directory
+pub fn cache_path() -> PathBuf {
+    config::get().cache_dir.value.join("http-cacache")
+}
+
+/// Cached responses, ordered by URL
+pub fn entries(cache: &Path) -> Result<Vec<Metadata>> {
+    // Listing fails on a cache without any entries ever written, or one that was cleared
+    let is_empty = fs::read_dir(cache).map_or(true, |mut dir| dir.next().is_none());
+    if is_empty {
+        return Ok(vec![]);
+    }
+    let mut entries = cacache::list_sync(cache).collect::<cacache::Result<Vec<_>>>()?;
+    entries.sort_by(|a, b| a.key.cmp(&b.key));
+    Ok(entries)
+}
+
+/// URL of the cached response, the key without the method
+pub fn url(entry: &Metadata) -> &str {
+    entry
+        .key
+        .split_once(':')
+        .map_or(entry.key.as_str(), |(_, url)| url)
+}
+
+pub fn render(entries: &[Metadata]) -> String {
+    let mut out = String::new();
+    for entry in entries {
+        let time = DateTime::from_timestamp_millis(entry.time as i64)
+            .map(|t| t.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+            .unwrap_or_default();
+        out.push_str(&format!("{}  {:>8}  {}\n", time, entry.size, url(entry)));
+    }
+    out.push_str(&format!("{} cached responses\n", entries.len()));
+    out
+}
+
+/// Whether the URL is the puzzle page or the input of the given day
+pub fn is_day_url(url: &str, year: u32, day: u32) -> bool {
+    let path = format!("/{}/day/{}", year, day);
+    url.split_once("://")
+        .and_then(|(_, rest)| rest.find('/').map(|i| &rest[i..]))
+        .and_then(|p| p.strip_prefix(&path))
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// Remove the cached responses of the given day, or all of them
+pub fn clear(cache: &Path, day: Option<(u32, u32)>) -> Result<usize> {
+    let entries = entries(cache)?;
+    let Some((year, day)) = day else {
+        if cache.exists() {
+            cacache::clear_sync(cache)?;
+        }
+        return Ok(entries.len());
+    };
+    let removed: Vec<Metadata> = entries
+        .into_iter()
+        .filter(|e| is_day_url(url(e), year, day))
+        .collect();
+    remove(cache, &removed)?;
+    Ok(removed.len())
+}
+
+/// Remove stale pages and any responses that shouldn't have been cached in the first place
+///
+/// Inputs are kept as they never change.
+pub async fn prune(cache: &Path) -> Result<usize> {
+    let manager = CACacheManager {
+        path: cache.to_path_buf(),
+    };
+    let now = SystemTime::now();
+    let mut removed = vec![];
+    for entry in entries(cache)? {
+        let keep = match manager.get(&entry.key).await {
+            Ok(Some((response, policy))) => {
+                let is_input = response.url.path().ends_with("/input");
+                let is_html = response
+                    .headers
+                    .get("content-type")
+                    .is_some_and(|v| v.starts_with("text/html"));
+                response.status == 200
+                    && (is_input || !policy.is_stale(now))
+                    && (!is_html || is_logged_in(&String::from_utf8_lossy(&response.body)))
+            }
+            // Unreadable entries
+            _ => false,
+        };
+        if !keep {
+            removed.push(entry);
+        }
+    }
+    remove(cache, &removed)?;
+    Ok(removed.len())
+}
+
+/// Remove the entries and the content no longer referenced by any other entry
+fn remove(cache: &Path, removed: &[Metadata]) -> Result<()> {
+    for entry in removed {
+        cacache::remove_sync(cache, &entry.key)?;
+    }
+    let referenced: HashSet<String> = entries(cache)?
+        .iter()
+        .map(|e| e.integrity.to_string())
+        .collect();
+    let mut orphaned = HashSet::new();
+    for entry in removed {
+        let integrity = entry.integrity.to_string();
+        if !referenced.contains(&integrity) && orphaned.insert(integrity) {
+            cacache::remove_hash_sync(cache, &entry.integrity)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(cache: &Path, url: &str, content: &str) {
+        cacache::write_sync(cache, format!("GET:{}", url), content).unwrap();
+    }
+
+    #[test]
+    fn test_is_day_url() {
+        let url = "https://adventofcode.com/2023/day/1";
+        assert!(is_day_url(url, 2023, 1));
+        assert!(is_day_url(&format!("{}/input", url), 2023, 1));
+        assert!(!is_day_url(url, 2023, 10));
+        assert!(!is_day_url(url, 2022, 1));
+        assert!(!is_day_url("https://adventofcode.com/2023/day/10", 2023, 1));
+        assert!(!is_day_url("https://adventofcode.com/2023", 2023, 1));
+    }
+
+    #[test]
+    fn test_clear_day() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = dir.path();
+        let base = "https://adventofcode.com/2023";
+        write(cache, &format!("{}/day/1", base), "day 1");
+        write(cache, &format!("{}/day/1/input", base), "1\n");
+        write(cache, &format!("{}/day/2", base), "day 2");
+        // Same content as the page of day 1, which has to stay readable
+        write(cache, base, "day 1");
+
+        assert_eq!(clear(cache, Some((2023, 1))).unwrap(), 2);
+        let urls: Vec<String> = entries(cache)
+            .unwrap()
+            .iter()
+            .map(|e| url(e).to_string())
+            .collect();
+        assert_eq!(urls, vec![base.to_string(), format!("{}/day/2", base)]);
+        assert_eq!(
+            cacache::read_sync(cache, format!("GET:{}", base)).unwrap(),
+            b"day 1"
+        );
+
+        assert_eq!(clear(cache, None).unwrap(), 2);
+        assert!(entries(cache).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_prune_unreadable() {
+        let dir = tempfile::tempdir().unwrap();
+        write(
+            dir.path(),
+            "https://adventofcode.com/2023",
+            "not a response",
+        );
+        assert_eq!(prune(dir.path()).await.unwrap(), 1);
+        assert!(entries(dir.path()).unwrap().is_empty());
+    }
+}
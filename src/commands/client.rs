@@ -1,16 +1,162 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
 use http_cache_reqwest::{CACacheManager, Cache, CacheMode, HttpCache, HttpCacheOptions};
-use reqwest::Client;
+use reqwest::header::{HeaderMap, HeaderValue, COOKIE, USER_AGENT};
+use reqwest::{Client, Response, StatusCode};
 use reqwest_middleware::{ClientBuilder, ClientWithMiddleware};
 
+use super::cache::cache_path;
+use super::config;
+use super::middleware::{NoStore, Offline, Retry, Throttle};
+use super::session::{self, Session};
+use crate::error::{AocError, Result};
+
 pub type AocClient = ClientWithMiddleware;
 
+pub const AOC_URL: &str = "https://adventofcode.com";
+
+/// Environment variable with contact details included in the User-Agent
+pub const CONTACT_ENV: &str = "AOCRS_CONTACT";
+/// Environment variable overriding the minimum interval between requests, in seconds
+pub const INTERVAL_ENV: &str = "AOCRS_MIN_INTERVAL";
+
+const DEFAULT_CONTACT: &str = "https://github.com/tzanetl/advent-of-code-2023";
+
+/// How the client identifies itself and throttles requests sent to Advent of Code
+#[derive(Debug, Clone)]
+pub struct ClientOptions {
+    pub contact: String,
+    pub min_interval: Duration,
+    pub max_retries: u32,
+    pub retry_delay: Duration,
+    /// File storing the time of the last request
+    pub throttle_state: PathBuf,
+    /// Answer only from the cache
+    pub offline: bool,
+    /// Directory of the HTTP cache
+    pub cache: PathBuf,
+}
+
+impl Default for ClientOptions {
+    fn default() -> Self {
+        Self {
+            contact: DEFAULT_CONTACT.to_string(),
+            min_interval: Duration::from_secs(3),
+            max_retries: 3,
+            retry_delay: Duration::from_secs(2),
+            throttle_state: config::get().cache_dir.value.join("last_request"),
+            offline: config::get().offline.value,
+            cache: cache_path(),
+        }
+    }
+}
+
+impl ClientOptions {
+    pub fn from_env() -> Self {
+        let mut options = Self::default();
+        if let Ok(contact) = std::env::var(CONTACT_ENV) {
+            options.contact = contact;
+        }
+        if let Some(seconds) = std::env::var(INTERVAL_ENV)
+            .ok()
+            .and_then(|s| s.parse::<f64>().ok())
+        {
+            options.min_interval = Duration::from_secs_f64(seconds.max(0.0));
+        }
+        options
+    }
+
+    pub fn user_agent(&self) -> String {
+        format!("aocrs/{} ({})", env!("CARGO_PKG_VERSION"), self.contact)
+    }
+}
+
 pub fn build_with_defaults() -> AocClient {
-    let client = ClientBuilder::new(Client::new())
+    build_with_mode(CacheMode::Default)
+}
+
+pub fn build_with_mode(mode: CacheMode) -> AocClient {
+    build(session::load().as_ref(), mode, &ClientOptions::from_env())
+}
+
+/// Client for personal inputs, which never change: once downloaded they are answered from
+/// the cache without revalidating them, even when the puzzle page is refreshed
+pub fn build_for_inputs(session: Option<&Session>, options: &ClientOptions) -> AocClient {
+    build(session, CacheMode::IgnoreRules, options)
+}
+
+/// Client caching pages with the given mode
+///
+/// Submitting an answer drops the cached page of the day as it changes once a part is
+/// solved. Error responses and pages served to a logged out user are never cached. Offline
+/// every request is answered from the cache or fails.
+pub fn build(session: Option<&Session>, mode: CacheMode, options: &ClientOptions) -> AocClient {
+    let offline = options.offline;
+    let mode = if offline {
+        CacheMode::OnlyIfCached
+    } else {
+        mode
+    };
+    let cache_options = HttpCacheOptions {
+        cache_bust: Some(Arc::new(|parts, _, _| {
+            let uri = parts.uri.to_string();
+            match uri.strip_suffix("/answer") {
+                Some(page) => vec![format!("GET:{}", page)],
+                None => vec![],
+            }
+        })),
+        ..HttpCacheOptions::default()
+    };
+    let mut builder = ClientBuilder::new(base_client(session, &options.user_agent()));
+    if offline {
+        builder = builder.with(Offline);
+    }
+    builder
         .with(Cache(HttpCache {
-            mode: CacheMode::Default,
-            manager: CACacheManager::default(),
-            options: HttpCacheOptions::default(),
+            mode,
+            manager: CACacheManager {
+                path: options.cache.clone(),
+            },
+            options: cache_options,
         }))
-        .build();
-    client
+        .with(NoStore)
+        .with(Retry::new(options.max_retries, options.retry_delay))
+        .with(Throttle::new(
+            options.min_interval,
+            options.throttle_state.clone(),
+        ))
+        .build()
+}
+
+/// Plain reqwest client sending the session cookie with every request
+pub fn base_client(session: Option<&Session>, user_agent: &str) -> Client {
+    let mut headers = HeaderMap::new();
+    if let Ok(user_agent) = HeaderValue::from_str(user_agent) {
+        headers.insert(USER_AGENT, user_agent);
+    }
+    if let Some(mut cookie) = session.and_then(|s| HeaderValue::from_str(&s.cookie()).ok()) {
+        cookie.set_sensitive(true);
+        headers.insert(COOKIE, cookie);
+    }
+    Client::builder()
+        .default_headers(headers)
+        .build()
+        .expect("Unable to build HTTP client")
+}
+
+/// Map the error statuses Advent of Code responds with for the given day into errors
+pub fn check_response(resp: Response, year: &u32, day: &u32) -> Result<Response> {
+    match resp.status() {
+        status if status.is_success() => Ok(resp),
+        StatusCode::BAD_REQUEST | StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+            Err(AocError::Unauthenticated)
+        }
+        StatusCode::NOT_FOUND => Err(AocError::NotUnlocked {
+            year: *year,
+            day: *day,
+        }),
+        status => Err(AocError::Http(status)),
+    }
 }
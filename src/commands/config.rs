@@ -0,0 +1,364 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+use super::workspace::find_root;
+use crate::error::{AocError, Result};
+
+/// Config file of the workspace, next to its `Cargo.toml`
+pub const CONFIG_FILE: &str = "aocrs.toml";
+
+pub const YEAR_ENV: &str = "AOCRS_YEAR";
+pub const DAY_DIR_ENV: &str = "AOCRS_DAY_DIR";
+pub const INPUT_FILE_ENV: &str = "AOCRS_INPUT_FILE";
+pub const TEST_FILE_ENV: &str = "AOCRS_TEST_FILE";
+pub const CACHE_DIR_ENV: &str = "AOCRS_CACHE_DIR";
+pub const SESSION_FILE_ENV: &str = "AOCRS_SESSION_FILE";
+pub const OFFLINE_ENV: &str = "AOCRS_OFFLINE";
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Where the value of a setting came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Default,
+    File(PathBuf),
+    Env(&'static str),
+    Cli,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Default => write!(f, "default"),
+            Self::File(path) => write!(f, "{}", path.display()),
+            Self::Env(var) => write!(f, "environment variable {}", var),
+            Self::Cli => write!(f, "command line"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Setting<T> {
+    pub value: T,
+    pub source: Source,
+}
+
+impl<T> Setting<T> {
+    fn default(value: T) -> Self {
+        Self {
+            value,
+            source: Source::Default,
+        }
+    }
+
+    fn set(&mut self, value: Option<T>, source: &Source) {
+        if let Some(value) = value {
+            self.value = value;
+            self.source = source.clone();
+        }
+    }
+}
+
+/// Settings of one config file, all optional
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ConfigFile {
+    year: Option<u32>,
+    day_dir: Option<String>,
+    input_file: Option<String>,
+    test_file: Option<String>,
+    cache_dir: Option<PathBuf>,
+    session_file: Option<PathBuf>,
+    offline: Option<bool>,
+}
+
+/// Effective settings, merged from the defaults, the user config, the workspace
+/// `aocrs.toml`, environment variables and command line arguments, later ones winning
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// Event year used when a command isn't given one
+    pub year: Setting<u32>,
+    /// Directory of a day relative to the workspace root, `{day}` or `{day:02}` being
+    /// replaced with the number of the day
+    pub day_dir: Setting<String>,
+    pub input_file: Setting<String>,
+    pub test_file: Setting<String>,
+    /// Directory for cached pages, leaderboards and request throttling
+    pub cache_dir: Setting<PathBuf>,
+    /// File the session token is stored in, unless given with `AOC_SESSION`
+    pub session_file: Setting<PathBuf>,
+    /// Only use cached pages and saved files, never the network
+    pub offline: Setting<bool>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            year: Setting::default(2023),
+            day_dir: Setting::default("day_{day:02}".to_string()),
+            input_file: Setting::default("input.txt".to_string()),
+            test_file: Setting::default("test.txt".to_string()),
+            cache_dir: Setting::default(dirs::cache_dir().unwrap_or_default().join("aocrs")),
+            session_file: Setting::default(
+                dirs::config_dir()
+                    .unwrap_or_default()
+                    .join("aocrs")
+                    .join("session"),
+            ),
+            offline: Setting::default(false),
+        }
+    }
+}
+
+/// Path of the user config, `<config dir>/aocrs/config.toml`
+pub fn user_config_file() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join("aocrs").join("config.toml"))
+}
+
+/// Path of the workspace config, in the workspace root or else the current directory
+pub fn workspace_config_file() -> PathBuf {
+    find_root().unwrap_or_default().join(CONFIG_FILE)
+}
+
+/// Use the config for the rest of the run, unless one is already in use
+pub fn init(config: Config) -> &'static Config {
+    CONFIG.get_or_init(|| config)
+}
+
+/// Config given to [`init`], or the defaults if it wasn't called
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(Config::default)
+}
+
+impl Config {
+    pub fn load() -> Result<Self> {
+        let mut config = Self::default();
+        let files = user_config_file()
+            .into_iter()
+            .chain([workspace_config_file()]);
+        for path in files {
+            if path.exists() {
+                config.apply_file(&fs::read_to_string(&path)?, Source::File(path))?;
+            }
+        }
+        config.apply_env(|var| std::env::var(var).ok())?;
+        Ok(config)
+    }
+
+    fn apply_file(&mut self, content: &str, source: Source) -> Result<()> {
+        let file: ConfigFile = toml::from_str(content)
+            .map_err(|e| AocError::Parse(format!("{}: {}", source, e.message())))?;
+        self.year.set(file.year, &source);
+        self.day_dir.set(file.day_dir, &source);
+        self.input_file.set(file.input_file, &source);
+        self.test_file.set(file.test_file, &source);
+        self.cache_dir.set(file.cache_dir, &source);
+        self.session_file.set(file.session_file, &source);
+        self.offline.set(file.offline, &source);
+        self.validate()
+    }
+
+    fn apply_env(&mut self, var: impl Fn(&'static str) -> Option<String>) -> Result<()> {
+        let year = var(YEAR_ENV)
+            .map(|year| {
+                year.trim()
+                    .parse()
+                    .map_err(|_| AocError::Parse(format!("{} isn't a year: {}", YEAR_ENV, year)))
+            })
+            .transpose()?;
+        let offline = var(OFFLINE_ENV)
+            .map(|offline| match offline.trim() {
+                "1" | "true" => Ok(true),
+                "0" | "false" | "" => Ok(false),
+                _ => Err(AocError::Parse(format!(
+                    "{} must be true or false: {}",
+                    OFFLINE_ENV, offline
+                ))),
+            })
+            .transpose()?;
+        self.year.set(year, &Source::Env(YEAR_ENV));
+        self.day_dir
+            .set(var(DAY_DIR_ENV), &Source::Env(DAY_DIR_ENV));
+        self.input_file
+            .set(var(INPUT_FILE_ENV), &Source::Env(INPUT_FILE_ENV));
+        self.test_file
+            .set(var(TEST_FILE_ENV), &Source::Env(TEST_FILE_ENV));
+        self.cache_dir.set(
+            var(CACHE_DIR_ENV).map(PathBuf::from),
+            &Source::Env(CACHE_DIR_ENV),
+        );
+        self.session_file.set(
+            var(SESSION_FILE_ENV).map(PathBuf::from),
+            &Source::Env(SESSION_FILE_ENV),
+        );
+        self.offline.set(offline, &Source::Env(OFFLINE_ENV));
+        self.validate()
+    }
+
+    /// Go offline when asked to on the command line
+    pub fn set_offline(&mut self, offline: bool) {
+        self.offline.set(offline.then_some(true), &Source::Cli);
+    }
+
+    /// Override the year with the one given on the command line
+    pub fn year_or(&self, year: Option<u32>) -> Setting<u32> {
+        let mut setting = self.year.clone();
+        setting.set(year, &Source::Cli);
+        setting
+    }
+
+    fn validate(&self) -> Result<()> {
+        if !self.day_dir.value.contains("{day}") && !self.day_dir.value.contains("{day:02}") {
+            return Err(AocError::Parse(format!(
+                "day_dir from {} must contain {{day}} or {{day:02}}: {}",
+                self.day_dir.source, self.day_dir.value
+            )));
+        }
+        Ok(())
+    }
+
+    /// Directory of the given day relative to the workspace root, e.g. `day_05`
+    pub fn day_dir(&self, day: u32) -> String {
+        self.day_dir
+            .value
+            .replace("{day:02}", &format!("{:02}", day))
+            .replace("{day}", &day.to_string())
+    }
+
+    /// Inverse of [`Config::day_dir`], the day of a directory following the pattern
+    pub fn parse_day_dir(&self, dir: &str) -> Option<u32> {
+        let (prefix, suffix) = ["{day:02}", "{day}"]
+            .iter()
+            .find_map(|placeholder| self.day_dir.value.split_once(placeholder))?;
+        let day = dir.strip_prefix(prefix)?.strip_suffix(suffix)?;
+        if day.is_empty() || !day.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        day.parse().ok()
+    }
+
+    /// Example input of part 2 when it differs from the first one, e.g. `test_pt2.txt`
+    pub fn test_file_pt2(&self) -> String {
+        let path = Path::new(&self.test_file.value);
+        let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+        match path.extension() {
+            Some(ext) => format!("{}_pt2.{}", stem, ext.to_string_lossy()),
+            None => format!("{}_pt2", stem),
+        }
+    }
+
+    /// Effective values and their sources, one per line
+    pub fn render(&self) -> String {
+        let rows = [
+            ("year", self.year.value.to_string(), &self.year.source),
+            ("day_dir", quote(&self.day_dir.value), &self.day_dir.source),
+            (
+                "input_file",
+                quote(&self.input_file.value),
+                &self.input_file.source,
+            ),
+            (
+                "test_file",
+                quote(&self.test_file.value),
+                &self.test_file.source,
+            ),
+            (
+                "cache_dir",
+                quote(&self.cache_dir.value.to_string_lossy()),
+                &self.cache_dir.source,
+            ),
+            (
+                "session_file",
+                quote(&self.session_file.value.to_string_lossy()),
+                &self.session_file.source,
+            ),
+            (
+                "offline",
+                self.offline.value.to_string(),
+                &self.offline.source,
+            ),
+        ];
+        rows.iter()
+            .map(|(key, value, source)| format!("{:<12} = {}  # {}\n", key, value, source))
+            .collect()
+    }
+}
+
+fn quote(value: &str) -> String {
+    toml::Value::String(value.to_string()).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn test_layers() {
+        let mut config = Config::default();
+        let user = Source::File(PathBuf::from("user.toml"));
+        let workspace = Source::File(PathBuf::from(CONFIG_FILE));
+        config
+            .apply_file("year = 2022\ntest_file = \"example.txt\"\n", user.clone())
+            .unwrap();
+        config
+            .apply_file("year = 2021\nday_dir = \"{day}\"\n", workspace.clone())
+            .unwrap();
+        let env = HashMap::from([
+            (YEAR_ENV, "2020"),
+            (INPUT_FILE_ENV, "data.txt"),
+            (OFFLINE_ENV, "1"),
+        ]);
+        config
+            .apply_env(|var| env.get(var).map(|v| v.to_string()))
+            .unwrap();
+
+        assert_eq!(config.year.value, 2020);
+        assert_eq!(config.year.source, Source::Env(YEAR_ENV));
+        assert_eq!(config.day_dir.source, workspace);
+        assert_eq!(config.test_file.source, user);
+        assert_eq!(config.input_file.value, "data.txt");
+        assert_eq!(config.cache_dir.source, Source::Default);
+        assert!(config.offline.value);
+        assert_eq!(config.year_or(Some(2019)).source, Source::Cli);
+        assert_eq!(config.year_or(None).value, 2020);
+    }
+
+    #[test]
+    fn test_invalid() {
+        let mut config = Config::default();
+        let source = Source::File(PathBuf::from(CONFIG_FILE));
+        assert!(config.apply_file("yaer = 2022", source.clone()).is_err());
+        assert!(config.apply_file("day_dir = \"days\"", source).is_err());
+        assert!(config
+            .apply_env(|var| (var == YEAR_ENV).then(|| "bwian".to_string()))
+            .is_err());
+    }
+
+    #[test]
+    fn test_day_dir() {
+        let mut config = Config::default();
+        assert_eq!(config.day_dir(5), "day_05");
+        assert_eq!(config.parse_day_dir("day_10"), Some(10));
+        assert_eq!(config.parse_day_dir("day_x"), None);
+        assert_eq!(config.parse_day_dir("utils"), None);
+
+        config.day_dir.value = "solutions/{day}-rs".to_string();
+        assert_eq!(config.day_dir(5), "solutions/5-rs");
+        assert_eq!(config.parse_day_dir("solutions/12-rs"), Some(12));
+        assert_eq!(config.parse_day_dir("solutions/-rs"), None);
+    }
+
+    #[test]
+    fn test_test_file_pt2() {
+        let mut config = Config::default();
+        assert_eq!(config.test_file_pt2(), "test_pt2.txt");
+        config.test_file.value = "example".to_string();
+        assert_eq!(config.test_file_pt2(), "example_pt2");
+    }
+}
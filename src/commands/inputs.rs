@@ -1,36 +1,267 @@
-use scraper::{Html, Selector};
+use std::path::Path;
+use std::time::Duration;
 
-use super::client::{build_with_defaults, AocClient};
+use http_cache_reqwest::CacheMode;
+use scraper::{ElementRef, Html, Selector};
+use serde::{Deserialize, Serialize};
 
-pub async fn load_inputs(year: u32, day: u32) -> Result<(), Box<dyn std::error::Error>> {
-    let client = build_with_defaults();
-    load_test_input(&client, &year, &day).await?;
-    Ok(())
+use super::client::{
+    build, build_for_inputs, build_with_mode, check_response, AocClient, ClientOptions, AOC_URL,
+};
+use super::config;
+use super::ledger::{ledger_path, Ledger};
+use super::session::{self, Session};
+use super::unlock::{retry_locked, validate_unlocked, wait_for_unlock, SystemClock};
+use super::workspace::day_dir;
+use crate::error::{AocError, Result};
+
+pub const EXPECTED_FILE: &str = "expected.toml";
+
+/// Examples and the expected example answer of one part of the puzzle
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct PuzzlePart {
+    pub examples: Vec<String>,
+    pub answer: Option<String>,
+}
+
+/// Known answers of the examples, stored in `expected.toml`
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Expected {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+/// Retries of loading the inputs right after the puzzle unlocked
+const UNLOCK_RETRIES: u32 = 5;
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct InputOptions {
+    /// Number of the example block to save as the test input
+    pub example: Option<usize>,
+    /// Fetch the puzzle page again instead of using the cache
+    pub refresh: bool,
+    /// Wait for the puzzle to unlock
+    pub wait: bool,
+}
+
+pub async fn load_inputs(year: u32, day: u32, options: &InputOptions) -> Result<()> {
+    let clock = SystemClock;
+    let retries = if options.wait {
+        wait_for_unlock(year, day, &clock).await?;
+        UNLOCK_RETRIES
+    } else {
+        validate_unlocked(year, day, &clock)?;
+        0
+    };
+
+    let dir = &day_dir(&day);
+    let config = config::get();
+    let saved = [&config.input_file.value, &config.test_file.value]
+        .iter()
+        .all(|file| dir.join(file).exists());
+    if config.offline.value && saved {
+        println!("Using saved inputs of day {}", day);
+        return Ok(());
+    }
+
+    let (client, input_client) = &clients(
+        options.refresh,
+        session::load().as_ref(),
+        &ClientOptions::from_env(),
+    );
+    std::fs::create_dir_all(dir)?;
+    // The inputs may still be unavailable for a moment after the unlock time
+    retry_locked(&clock, retries, Duration::from_secs(1), || async move {
+        load_test_input(client, &year, &day, dir, options.example).await?;
+        load_personal_input(input_client, AOC_URL, &year, &day, dir).await
+    })
+    .await
+}
+
+/// Clients for the puzzle page, refetched when refreshing, and for the personal input
+fn clients(
+    refresh: bool,
+    session: Option<&Session>,
+    options: &ClientOptions,
+) -> (AocClient, AocClient) {
+    let mode = if refresh {
+        CacheMode::Reload
+    } else {
+        CacheMode::Default
+    };
+    (
+        build(session, mode, options),
+        build_for_inputs(session, options),
+    )
 }
 
 async fn load_personal_input(
     client: &AocClient,
+    base_url: &str,
     year: &u32,
     day: &u32,
-) -> Result<(), Box<dyn std::error::Error>> {
-    todo!()
+    dir: &Path,
+) -> Result<()> {
+    let url = format!("{}/{}/day/{}/input", base_url, year, day);
+    let resp = check_response(client.get(url).send().await?, year, day)?;
+
+    let input = resp.text().await?;
+    std::fs::write(dir.join(&config::get().input_file.value), input)?;
+    println!("Saved input of day {}", day);
+    Ok(())
 }
 
 async fn load_test_input(
     client: &AocClient,
     year: &u32,
     day: &u32,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let url = format!("https://adventofcode.com/{}/day/{}", year, day);
-    let resp = client.get(url).send().await?;
-
-    let document = Html::parse_document(&resp.text().await?);
-    let selector = Selector::parse("pre>code")?;
-    let example_input = document
-        .select(&selector)
-        .next()
-        .expect("No code node found")
-        .inner_html();
-    std::fs::write("test.txt", example_input)?;
+    dir: &Path,
+    example: Option<usize>,
+) -> Result<()> {
+    let url = format!("{}/{}/day/{}", AOC_URL, year, day);
+    let resp = check_response(client.get(&url).send().await?, year, day)?;
+    let mut parts = parse_puzzle(&resp.text().await?)?;
+
+    // The cached page may predate solving part 1 and thus miss part 2
+    let ledger = Ledger::load(&ledger_path())?;
+    if parts.len() < 2 && ledger.get(year, day, &1).is_some_and(|r| r.solved) {
+        let resp = build_with_mode(CacheMode::Reload).get(&url).send().await?;
+        let resp = check_response(resp, year, day)?;
+        parts = parse_puzzle(&resp.text().await?)?;
+    }
+
+    let blocks: Vec<&String> = parts.iter().flat_map(|p| p.examples.iter()).collect();
+    for (i, block) in blocks.iter().enumerate() {
+        let first_line = block.lines().next().unwrap_or_default();
+        println!("Example {}: {}", i + 1, first_line);
+    }
+    let test_input = match example {
+        Some(n) => blocks
+            .get(n - 1)
+            .ok_or(format!("No example {}, found {}", n, blocks.len()))?,
+        None => blocks
+            .first()
+            .ok_or(AocError::Parse("No example found".to_string()))?,
+    };
+    let config = config::get();
+    std::fs::write(dir.join(&config.test_file.value), test_input)?;
+
+    // Part 2 only has an example of its own if it differs from the first one
+    if let Some(example_pt2) = parts
+        .get(1)
+        .and_then(|p| p.examples.first())
+        .filter(|e| e != test_input)
+    {
+        std::fs::write(dir.join(config.test_file_pt2()), example_pt2)?;
+    }
+
+    let expected = Expected {
+        part_1: parts.first().and_then(|p| p.answer.clone()),
+        part_2: parts.get(1).and_then(|p| p.answer.clone()),
+    };
+    if expected != Expected::default() {
+        std::fs::write(dir.join(EXPECTED_FILE), toml::to_string(&expected)?)?;
+    }
     Ok(())
 }
+
+/// Parse the examples and their answers from each part of the puzzle description
+pub fn parse_puzzle(html: &str) -> Result<Vec<PuzzlePart>> {
+    let document = Html::parse_document(html);
+    let article_selector = Selector::parse("article.day-desc")?;
+    let example_selector = Selector::parse("pre > code")?;
+    // The answer to the example is by convention the last emphasised code of the part
+    let answer_selector = Selector::parse("code > em, em > code")?;
+
+    let parts = document
+        .select(&article_selector)
+        .map(|article| PuzzlePart {
+            examples: article.select(&example_selector).map(text).collect(),
+            answer: article
+                .select(&answer_selector)
+                .last()
+                .map(|answer| text(answer).trim().to_string()),
+        })
+        .collect();
+    Ok(parts)
+}
+
+fn text(element: ElementRef) -> String {
+    element.text().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PUZZLE: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2>
+<p>For example:</p>
+<pre><code>1abc2
+a1b2c3
+</code></pre>
+<p>Adding these together produces <code><em>142</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>54601</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<pre><code>two1nine
+<em>4</em>nineeightseven2
+</code></pre>
+<p>Adding these together produces <em><code>281</code></em>.</p>
+</article>
+</main></body></html>"#;
+
+    #[test]
+    fn test_parse_puzzle() {
+        let parts = parse_puzzle(PUZZLE).unwrap();
+        assert_eq!(
+            parts,
+            vec![
+                PuzzlePart {
+                    examples: vec!["1abc2\na1b2c3\n".to_string()],
+                    answer: Some("142".to_string()),
+                },
+                PuzzlePart {
+                    examples: vec!["two1nine\n4nineeightseven2\n".to_string()],
+                    answer: Some("281".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_puzzle_no_articles() {
+        assert!(parse_puzzle("<html></html>").unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_personal_input_cached() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/2023/day/1/input")
+            .with_header("content-type", "text/plain")
+            .with_body("1abc2\n")
+            .expect(1)
+            .create_async()
+            .await;
+        let dir = tempfile::tempdir().unwrap();
+        let options = ClientOptions {
+            min_interval: Duration::ZERO,
+            throttle_state: dir.path().join("last_request"),
+            offline: false,
+            cache: dir.path().join("cache"),
+            ..ClientOptions::default()
+        };
+
+        // Downloading again, even with `--refresh`, is answered from the cache
+        for refresh in [false, true] {
+            let (_, client) = clients(refresh, None, &options);
+            load_personal_input(&client, &server.url(), &2023, &1, dir.path())
+                .await
+                .unwrap();
+        }
+        let input = std::fs::read_to_string(dir.path().join(&config::get().input_file.value));
+        assert_eq!(input.unwrap(), "1abc2\n");
+        mock.assert_async().await;
+    }
+}
@@ -0,0 +1,246 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use chrono::DateTime;
+use serde::Deserialize;
+
+use super::client::{build_with_defaults, AOC_URL};
+use super::config;
+use super::unlock::unlock_time;
+use crate::error::{AocError, Result};
+
+/// Advent of Code asks not to fetch a leaderboard more often than every 15 minutes
+const CACHE_TIME: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Debug, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub members: HashMap<String, Member>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u32,
+    pub last_star_ts: i64,
+    /// Stars per day and part
+    pub completion_day_level: HashMap<String, HashMap<String, Star>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Star {
+    pub get_star_ts: i64,
+}
+
+impl Leaderboard {
+    /// Members by local score, ties broken by stars and then by who got them first
+    pub fn ranked(&self) -> Vec<&Member> {
+        let mut members: Vec<&Member> = self.members.values().collect();
+        members.sort_by(|a, b| {
+            b.local_score
+                .cmp(&a.local_score)
+                .then(b.stars.cmp(&a.stars))
+                .then(a.last_star_ts.cmp(&b.last_star_ts))
+                .then(a.id.cmp(&b.id))
+        });
+        members
+    }
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => format!("(anonymous user #{})", self.id),
+        }
+    }
+
+    pub fn star(&self, day: u32, part: u8) -> Option<&Star> {
+        self.completion_day_level
+            .get(&day.to_string())?
+            .get(&part.to_string())
+    }
+
+    /// Time from the unlock of the puzzle to getting the star
+    pub fn completion_time(&self, year: u32, day: u32, part: u8) -> Option<Duration> {
+        let star = DateTime::from_timestamp(self.star(day, part)?.get_star_ts, 0)?;
+        (star - unlock_time(year, day)).to_std().ok()
+    }
+}
+
+pub async fn show_leaderboard(year: u32, id: u64, day: Option<u32>) -> Result<()> {
+    let leaderboard = load(year, id).await?;
+    match day {
+        Some(day) => print!("{}", render_day(&leaderboard, year, day)),
+        None => print!("{}", render(&leaderboard)),
+    }
+    Ok(())
+}
+
+fn cache_file(year: u32, id: u64) -> PathBuf {
+    config::get()
+        .cache_dir
+        .value
+        .join("leaderboard")
+        .join(format!("{}-{}.json", year, id))
+}
+
+/// Load the leaderboard, fetching it only if the stored copy is older than 15 minutes
+///
+/// Offline the stored copy is used however old it is.
+async fn load(year: u32, id: u64) -> Result<Leaderboard> {
+    let path = cache_file(year, id);
+    let age = fs::metadata(&path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok());
+    let offline = config::get().offline.value;
+    if age.is_some_and(|age| age < CACHE_TIME || offline) {
+        return Ok(serde_json::from_str(&fs::read_to_string(&path)?)?);
+    }
+
+    let url = format!("{}/{}/leaderboard/private/view/{}.json", AOC_URL, year, id);
+    if offline {
+        return Err(AocError::Offline(url));
+    }
+    let resp = build_with_defaults().get(url).send().await?;
+    if !resp.status().is_success() {
+        return Err(AocError::Http(resp.status()));
+    }
+    let content = resp.text().await?;
+    // Without access the leaderboard page is served instead of the JSON
+    let leaderboard: Leaderboard = serde_json::from_str(&content).map_err(|_| {
+        AocError::Parse(format!(
+            "No access to private leaderboard {} of {}",
+            id, year
+        ))
+    })?;
+
+    fs::create_dir_all(path.parent().unwrap())?;
+    fs::write(&path, content)?;
+    Ok(leaderboard)
+}
+
+/// Ranked table with the stars of each day, `*` for both parts and `+` for the first one
+pub fn render(leaderboard: &Leaderboard) -> String {
+    let mut out = format!(
+        "{:>4} {:>6} {:>5}  {:<25}  Name\n",
+        "", "Score", "Stars", "Days"
+    );
+    for (rank, member) in leaderboard.ranked().iter().enumerate() {
+        let days: String = (1..=25)
+            .map(|day| match (member.star(day, 1), member.star(day, 2)) {
+                (Some(_), Some(_)) => '*',
+                (Some(_), None) => '+',
+                _ => '.',
+            })
+            .collect();
+        out.push_str(&format!(
+            "{:>3}) {:>6} {:>5}  {}  {}\n",
+            rank + 1,
+            member.local_score,
+            member.stars,
+            days,
+            member.display_name()
+        ));
+    }
+    out
+}
+
+/// Completion times of both parts of the day, ordered by who finished first
+pub fn render_day(leaderboard: &Leaderboard, year: u32, day: u32) -> String {
+    let year: u32 = leaderboard.event.parse().unwrap_or(year);
+    let mut members: Vec<(&Member, Option<Duration>, Option<Duration>)> = leaderboard
+        .members
+        .values()
+        .map(|m| {
+            (
+                m,
+                m.completion_time(year, day, 1),
+                m.completion_time(year, day, 2),
+            )
+        })
+        .filter(|(_, part_1, _)| part_1.is_some())
+        .collect();
+    members.sort_by_key(|(m, part_1, part_2)| (part_2.is_none(), *part_2, *part_1, m.id));
+
+    let mut out = format!("Day {:>2} {:>10} {:>10}  Name\n", day, "Part 1", "Part 2");
+    for (rank, (member, part_1, part_2)) in members.iter().enumerate() {
+        out.push_str(&format!(
+            "{:>5}) {:>10} {:>10}  {}\n",
+            rank + 1,
+            format_duration(*part_1),
+            format_duration(*part_2),
+            member.display_name()
+        ));
+    }
+    out
+}
+
+fn format_duration(duration: Option<Duration>) -> String {
+    let Some(duration) = duration else {
+        return "-".to_string();
+    };
+    let seconds = duration.as_secs();
+    match seconds / 86400 {
+        0 => format!(
+            "{:02}:{:02}:{:02}",
+            seconds / 3600,
+            seconds % 3600 / 60,
+            seconds % 60
+        ),
+        days => format!("> {}d", days),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = include_str!("../../tests/fixtures/leaderboard.json");
+
+    #[test]
+    fn test_ranked() {
+        let leaderboard: Leaderboard = serde_json::from_str(FIXTURE).unwrap();
+        let names: Vec<String> = leaderboard
+            .ranked()
+            .iter()
+            .map(|m| m.display_name())
+            .collect();
+        assert_eq!(names, vec!["bwian", "(anonymous user #1002)", "Brian"]);
+    }
+
+    #[test]
+    fn test_render() {
+        let leaderboard: Leaderboard = serde_json::from_str(FIXTURE).unwrap();
+        let lines: Vec<String> = render(&leaderboard).lines().map(String::from).collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[1],
+            "  1)      7     3  *+.......................  bwian"
+        );
+        assert_eq!(
+            lines[3],
+            "  3)      0     0  .........................  Brian"
+        );
+    }
+
+    #[test]
+    fn test_render_day() {
+        let leaderboard: Leaderboard = serde_json::from_str(FIXTURE).unwrap();
+        assert_eq!(
+            render_day(&leaderboard, 2023, 1),
+            "Day  1     Part 1     Part 2  Name\n\
+             \x20   1)   00:05:00   00:10:00  (anonymous user #1002)\n\
+             \x20   2)   00:30:00   01:00:00  bwian\n"
+        );
+        assert_eq!(
+            render_day(&leaderboard, 2023, 2).lines().nth(1).unwrap(),
+            "    1)   01:15:00          -  bwian"
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use super::submit::Verdict;
+use super::workspace::find_root;
+use crate::error::Result;
+
+pub const LEDGER_FILE: &str = "ledger.json";
+
+/// Ledger file at the root of the workspace, or in the current directory outside of one
+pub fn ledger_path() -> PathBuf {
+    find_root().unwrap_or_default().join(LEDGER_FILE)
+}
+
+/// Submitted answers and their verdicts per year, day and part
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Ledger {
+    years: BTreeMap<u32, BTreeMap<u32, BTreeMap<u8, PartRecord>>>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct PartRecord {
+    pub solved: bool,
+    /// Correct answer, if it was submitted through aocrs
+    pub answer: Option<String>,
+    /// Highest answer known to be too low
+    pub too_low: Option<i64>,
+    /// Lowest answer known to be too high
+    pub too_high: Option<i64>,
+    pub submissions: Vec<Submission>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Submission {
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the Unix epoch
+    pub timestamp: u64,
+}
+
+impl Ledger {
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    pub fn get(&self, year: &u32, day: &u32, part: &u8) -> Option<&PartRecord> {
+        self.years.get(year)?.get(day)?.get(part)
+    }
+
+    /// Check that submitting the answer can't be known to fail beforehand
+    pub fn check(&self, year: &u32, day: &u32, part: &u8, answer: &str) -> Result<()> {
+        let Some(record) = self.get(year, day, part) else {
+            return Ok(());
+        };
+        if record.solved {
+            return match &record.answer {
+                Some(correct) => Err(format!("Part already solved with answer {}", correct).into()),
+                None => Err("Part already solved".into()),
+            };
+        }
+        if let Some(previous) = record
+            .submissions
+            .iter()
+            .find(|s| s.answer == answer && s.verdict.is_wrong())
+        {
+            return Err(format!("{} was already submitted: {}", answer, previous.verdict).into());
+        }
+        if let Ok(value) = answer.parse::<i64>() {
+            if let Some(low) = record.too_low.filter(|low| value <= *low) {
+                return Err(format!("{} is too low, {} already was", answer, low).into());
+            }
+            if let Some(high) = record.too_high.filter(|high| value >= *high) {
+                return Err(format!("{} is too high, {} already was", answer, high).into());
+            }
+        }
+        Ok(())
+    }
+
+    pub fn record(&mut self, year: u32, day: u32, part: u8, answer: &str, verdict: Verdict) {
+        let record = self
+            .years
+            .entry(year)
+            .or_default()
+            .entry(day)
+            .or_default()
+            .entry(part)
+            .or_default();
+
+        let value = answer.parse::<i64>().ok();
+        match verdict {
+            Verdict::Correct => {
+                record.solved = true;
+                record.answer = Some(answer.to_string());
+            }
+            Verdict::TooLow => {
+                record.too_low = record.too_low.max(value);
+            }
+            Verdict::TooHigh => {
+                record.too_high = match (record.too_high, value) {
+                    (Some(high), Some(value)) => Some(high.min(value)),
+                    (high, value) => high.or(value),
+                };
+            }
+            // Doesn't tell whether the part was solved
+            Verdict::Wrong | Verdict::RateLimited(_) | Verdict::WrongLevel => {}
+        }
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        record.submissions.push(Submission {
+            answer: answer.to_string(),
+            verdict,
+            timestamp,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_bounds() {
+        let mut ledger = Ledger::default();
+        ledger.record(2023, 1, 1, "100", Verdict::TooLow);
+        ledger.record(2023, 1, 1, "50", Verdict::TooLow);
+        ledger.record(2023, 1, 1, "200", Verdict::TooHigh);
+        ledger.record(2023, 1, 1, "300", Verdict::TooHigh);
+
+        let record = ledger.get(&2023, &1, &1).unwrap();
+        assert_eq!(record.too_low, Some(100));
+        assert_eq!(record.too_high, Some(200));
+
+        assert!(ledger.check(&2023, &1, &1, "100").is_err());
+        assert!(ledger.check(&2023, &1, &1, "250").is_err());
+        assert!(ledger.check(&2023, &1, &1, "150").is_ok());
+        assert!(ledger.check(&2023, &1, &2, "100").is_ok());
+    }
+
+    #[test]
+    fn test_check_wrong_and_solved() {
+        let mut ledger = Ledger::default();
+        ledger.record(2023, 7, 2, "abc", Verdict::Wrong);
+        ledger.record(2023, 7, 2, "abd", Verdict::RateLimited(None));
+        assert!(ledger.check(&2023, &7, &2, "abc").is_err());
+        assert!(ledger.check(&2023, &7, &2, "abd").is_ok());
+
+        ledger.record(2023, 7, 2, "abd", Verdict::Correct);
+        assert!(ledger.get(&2023, &7, &2).unwrap().solved);
+        assert!(ledger.check(&2023, &7, &2, "abe").is_err());
+    }
+
+    #[test]
+    fn test_check_wrong_level() {
+        let mut ledger = Ledger::default();
+        ledger.record(2023, 8, 2, "14", Verdict::WrongLevel);
+        assert!(!ledger.get(&2023, &8, &2).unwrap().solved);
+        assert!(ledger.check(&2023, &8, &2, "15").is_ok());
+    }
+
+    #[test]
+    fn test_deserialize_already_solved() {
+        let verdict: Verdict = serde_json::from_str("\"AlreadySolved\"").unwrap();
+        assert_eq!(verdict, Verdict::WrongLevel);
+    }
+
+    #[test]
+    fn test_serde_round_trip() {
+        let mut ledger = Ledger::default();
+        ledger.record(2023, 5, 1, "42", Verdict::TooHigh);
+        let json = serde_json::to_string(&ledger).unwrap();
+        let ledger: Ledger = serde_json::from_str(&json).unwrap();
+        assert_eq!(ledger.get(&2023, &5, &1).unwrap().too_high, Some(42));
+    }
+}
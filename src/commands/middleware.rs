@@ -0,0 +1,377 @@
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use reqwest::header::{HeaderValue, CACHE_CONTROL, CONTENT_TYPE};
+use reqwest::{Method, Request, Response, ResponseBuilderExt, StatusCode};
+use reqwest_middleware::{Middleware, Next, Result};
+use task_local_extensions::Extensions;
+use tokio::sync::Mutex;
+
+use super::session::is_logged_in;
+use crate::error::AocError;
+
+/// Header the cache sets to tell whether it had the response
+const XCACHELOOKUP: &str = "x-cache-lookup";
+
+/// Keeps at least `min_interval` between requests sent to the network
+///
+/// The time of the last request is stored in `state` so that the interval also holds across
+/// separate runs of aocrs.
+pub struct Throttle {
+    min_interval: Duration,
+    state: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl Throttle {
+    pub fn new(min_interval: Duration, state: PathBuf) -> Self {
+        Self {
+            min_interval,
+            state,
+            lock: Mutex::new(()),
+        }
+    }
+
+    fn last_request(&self) -> Option<Duration> {
+        let millis: u64 = fs::read_to_string(&self.state).ok()?.trim().parse().ok()?;
+        Some(Duration::from_millis(millis))
+    }
+
+    fn store_request(&self, at: Duration) {
+        if let Some(parent) = self.state.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        // Failing to persist only means the next run can't wait for this request
+        let _ = fs::write(&self.state, at.as_millis().to_string());
+    }
+}
+
+fn now() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+#[async_trait::async_trait]
+impl Middleware for Throttle {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> Result<Response> {
+        {
+            let _guard = self.lock.lock().await;
+            if let Some(last) = self.last_request() {
+                let wait = (last + self.min_interval).saturating_sub(now());
+                if !wait.is_zero() {
+                    tokio::time::sleep(wait).await;
+                }
+            }
+            self.store_request(now());
+        }
+        next.run(req, extensions).await
+    }
+}
+
+/// Retries requests failing with a server error, doubling the delay after each attempt
+///
+/// Only `GET` and `HEAD` requests are retried: a server error on submitting an answer
+/// doesn't mean that it wasn't received, and sending it again may be penalised.
+pub struct Retry {
+    max_retries: u32,
+    delay: Duration,
+}
+
+impl Retry {
+    pub fn new(max_retries: u32, delay: Duration) -> Self {
+        Self { max_retries, delay }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for Retry {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> Result<Response> {
+        if !matches!(*req.method(), Method::GET | Method::HEAD) {
+            return next.run(req, extensions).await;
+        }
+        let mut delay = self.delay;
+        for _ in 0..self.max_retries {
+            // Requests with streaming bodies can't be sent again
+            let Some(retry) = req.try_clone() else {
+                break;
+            };
+            let resp = next.clone().run(retry, extensions).await?;
+            if !resp.status().is_server_error() {
+                return Ok(resp);
+            }
+            tokio::time::sleep(delay).await;
+            delay *= 2;
+        }
+        next.run(req, extensions).await
+    }
+}
+
+/// Marks error responses and pages served to a logged out user as not to be stored, so
+/// that the cache never holds them in place of the real page
+///
+/// Goes between the cache and the network, as the cache decides whether to store a response
+/// from its `Cache-Control` header.
+pub struct NoStore;
+
+#[async_trait::async_trait]
+impl Middleware for NoStore {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> Result<Response> {
+        let mut resp = next.run(req, extensions).await?;
+        let is_html = resp
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.starts_with("text/html"));
+        if resp.status().is_success() && is_html {
+            // The body has to be read to check it, so the response is built again from it
+            let url = resp.url().clone();
+            let mut builder = http::Response::builder()
+                .status(resp.status())
+                .version(resp.version())
+                .url(url);
+            if let Some(headers) = builder.headers_mut() {
+                *headers = resp.headers().clone();
+            }
+            let body = resp.bytes().await?;
+            let logged_in = is_logged_in(&String::from_utf8_lossy(&body));
+            resp = builder
+                .body(body)
+                .map_err(|e| reqwest_middleware::Error::Middleware(e.into()))?
+                .into();
+            if logged_in {
+                return Ok(resp);
+            }
+        } else if resp.status().is_success() {
+            return Ok(resp);
+        }
+        resp.headers_mut()
+            .insert(CACHE_CONTROL, HeaderValue::from_static("no-store"));
+        Ok(resp)
+    }
+}
+
+/// Fails requests that the cache can't answer when offline
+///
+/// Goes before a cache in `OnlyIfCached` mode, which responds to a miss with a gateway
+/// timeout but lets requests other than `GET` through to the network.
+pub struct Offline;
+
+#[async_trait::async_trait]
+impl Middleware for Offline {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> Result<Response> {
+        let url = req.url().to_string();
+        if req.method() != Method::GET {
+            return Err(reqwest_middleware::Error::middleware(AocError::Offline(
+                format!("{} {}", req.method(), url),
+            )));
+        }
+        let resp = next.run(req, extensions).await?;
+        let miss = resp
+            .headers()
+            .get(XCACHELOOKUP)
+            .is_some_and(|v| v.as_bytes() == b"MISS");
+        if miss && resp.status() == StatusCode::GATEWAY_TIMEOUT {
+            return Err(reqwest_middleware::Error::middleware(AocError::Offline(
+                url,
+            )));
+        }
+        Ok(resp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use reqwest::Client;
+    use reqwest_middleware::ClientBuilder;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_throttle() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server.mock("GET", "/").expect(3).create_async().await;
+        let dir = tempfile::tempdir().unwrap();
+        let state = dir.path().join("last_request");
+
+        let interval = Duration::from_millis(100);
+        let client = ClientBuilder::new(Client::new())
+            .with(Throttle::new(interval, state.clone()))
+            .build();
+        let start = Instant::now();
+        for _ in 0..3 {
+            client.get(server.url()).send().await.unwrap();
+        }
+        assert!(start.elapsed() >= interval * 2);
+        assert!(state.exists());
+        mock.assert_async().await;
+
+        // A new client waits for the request made by the previous one
+        let client = ClientBuilder::new(Client::new())
+            .with(Throttle::new(Duration::from_secs(60), state))
+            .build();
+        let request = client.get(server.url()).send();
+        assert!(tokio::time::timeout(Duration::from_millis(100), request)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_retry_server_error() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/")
+            .with_status(502)
+            .expect(3)
+            .create_async()
+            .await;
+
+        let client = ClientBuilder::new(Client::new())
+            .with(Retry::new(2, Duration::from_millis(1)))
+            .build();
+        let resp = client.get(server.url()).send().await.unwrap();
+        assert_eq!(resp.status(), 502);
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_retry_post() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/answer")
+            .with_status(502)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = ClientBuilder::new(Client::new())
+            .with(Retry::new(2, Duration::from_millis(1)))
+            .build();
+        let resp = client
+            .post(format!("{}/answer", server.url()))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 502);
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_retry_client_error() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/")
+            .with_status(404)
+            .expect(1)
+            .create_async()
+            .await;
+
+        let client = ClientBuilder::new(Client::new())
+            .with(Retry::new(2, Duration::from_millis(1)))
+            .build();
+        let resp = client.get(server.url()).send().await.unwrap();
+        assert_eq!(resp.status(), 404);
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_no_store() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/user")
+            .with_header("content-type", "text/html")
+            .with_body(r#"<header><div class="user">bwian</div></header>"#)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/login")
+            .with_header("content-type", "text/html")
+            .with_body(r#"<header><a href="/2023/auth/login">[Log In]</a></header>"#)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/input")
+            .with_header("content-type", "text/plain")
+            .with_body("1\n2\n")
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/error")
+            .with_status(500)
+            .create_async()
+            .await;
+
+        let client = ClientBuilder::new(Client::new()).with(NoStore).build();
+        for (path, stored) in [
+            ("/user", true),
+            ("/login", false),
+            ("/input", true),
+            ("/error", false),
+        ] {
+            let resp = client
+                .get(format!("{}{}", server.url(), path))
+                .send()
+                .await
+                .unwrap();
+            let no_store = resp.headers().get(CACHE_CONTROL).is_some();
+            assert_eq!(no_store, !stored, "{}", path);
+            // The body is still readable after being checked
+            assert!(!resp.text().await.unwrap().is_empty() || !stored);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_offline() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("POST", "/answer")
+            .expect(0)
+            .create_async()
+            .await;
+        server
+            .mock("GET", "/miss")
+            .with_status(504)
+            .with_header(XCACHELOOKUP, "MISS")
+            .create_async()
+            .await;
+        server.mock("GET", "/hit").create_async().await;
+
+        let client = ClientBuilder::new(Client::new()).with(Offline).build();
+        let post = client.post(format!("{}/answer", server.url())).send().await;
+        assert!(matches!(
+            post.map_err(AocError::from),
+            Err(AocError::Offline(_))
+        ));
+        let miss = client.get(format!("{}/miss", server.url())).send().await;
+        assert!(matches!(
+            miss.map_err(AocError::from),
+            Err(AocError::Offline(url)) if url.ends_with("/miss")
+        ));
+        let hit = client.get(format!("{}/hit", server.url())).send().await;
+        assert!(hit.is_ok());
+        mock.assert_async().await;
+    }
+}
@@ -1,2 +1,17 @@
+pub mod bench;
+pub mod cache;
 pub mod client;
+pub mod config;
 pub mod inputs;
+pub mod leaderboard;
+pub mod ledger;
+pub mod middleware;
+pub mod new;
+pub mod read;
+pub mod run;
+pub mod session;
+pub mod stats;
+pub mod submit;
+pub mod unlock;
+pub mod verify;
+pub mod workspace;
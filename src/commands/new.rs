@@ -0,0 +1,135 @@
+use std::fs;
+use std::path::Path;
+
+use toml_edit::{Array, DocumentMut, Value};
+
+use super::config::{self, Config};
+use super::inputs::{load_inputs, InputOptions};
+use super::workspace::{day_dir, day_name, days, find_root};
+use crate::error::Result;
+
+const BOILERPLATE: &str = include_str!("../../boilerplate.rs");
+
+const MANIFEST_TEMPLATE: &str = r#"[package]
+name = "{name}"
+version = "0.1.0"
+edition = "2021"
+
+# See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html
+
+[dependencies]
+utils.workspace = true
+log.workspace = true
+"#;
+
+/// Create the crate of a new day from the boilerplate and optionally download its inputs
+pub async fn new_day(year: u32, day: u32, inputs: bool) -> Result<()> {
+    let root = find_root().ok_or("Not inside a Cargo workspace")?;
+    let dir = day_dir(&day);
+    if dir.exists() || days()?.iter().any(|(d, _)| *d == day) {
+        return Err(format!("{} already exists", dir.display()).into());
+    }
+    let name = day_name(&day);
+
+    fs::create_dir_all(dir.join("src"))?;
+    fs::write(
+        dir.join("Cargo.toml"),
+        MANIFEST_TEMPLATE.replace("{name}", &name),
+    )?;
+    fs::write(dir.join("src").join("main.rs"), BOILERPLATE)?;
+    println!("Created {}", dir.display());
+
+    let member = config::get().day_dir(day);
+    if register_member(&root.join("Cargo.toml"), &member, day)? {
+        println!("Added {} to workspace members", member);
+    }
+
+    if inputs {
+        load_inputs(year, day, &InputOptions::default()).await?;
+    }
+    Ok(())
+}
+
+/// Add the crate of the day to the workspace members of the manifest
+fn register_member(manifest: &Path, member: &str, day: u32) -> Result<bool> {
+    let content = fs::read_to_string(manifest)?;
+    match add_member(&content, member, day, config::get())? {
+        Some(updated) => {
+            fs::write(manifest, updated)?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+fn add_member(content: &str, member: &str, day: u32, config: &Config) -> Result<Option<String>> {
+    let mut doc: DocumentMut = content.parse()?;
+    let Some(workspace) = doc.get_mut("workspace").and_then(|w| w.as_table_like_mut()) else {
+        return Ok(None);
+    };
+    let members = workspace
+        .entry("members")
+        .or_insert(toml_edit::value(Array::new()))
+        .as_array_mut()
+        .ok_or("Workspace members is not an array")?;
+    if members.iter().any(|m| m.as_str() == Some(member)) {
+        return Ok(None);
+    }
+
+    // Keep the days in order, after any other members
+    let days: Vec<(usize, u32)> = members
+        .iter()
+        .enumerate()
+        .filter_map(|(i, m)| Some((i, config.parse_day_dir(m.as_str()?)?)))
+        .collect();
+    let index = match days.iter().find(|(_, d)| *d > day) {
+        Some((i, _)) => *i,
+        None => days.last().map_or(members.len(), |(i, _)| i + 1),
+    };
+
+    // Format the new member like its neighbour so multi-line arrays stay multi-line
+    let mut member = Value::from(member);
+    let previous = index.checked_sub(1).and_then(|i| members.get(i));
+    if let Some(neighbour) = members.get(index).or(previous) {
+        *member.decor_mut() = neighbour.decor().clone();
+    }
+    members.insert_formatted(index, member);
+    Ok(Some(doc.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_member() {
+        let content = "[workspace]\nmembers = [\"utils\", \"day_01\", \"day_03\"]\n";
+        let updated = add_member(content, "day_02", 2, &Config::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            updated,
+            "[workspace]\nmembers = [\"utils\", \"day_01\", \"day_02\", \"day_03\"]\n"
+        );
+        assert!(add_member(&updated, "day_02", 2, &Config::default())
+            .unwrap()
+            .is_none());
+
+        let content = "[workspace]\nmembers = [\n    \"utils\",\n    \"day_01\",\n]\n";
+        let updated = add_member(content, "day_02", 2, &Config::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            updated,
+            "[workspace]\nmembers = [\n    \"utils\",\n    \"day_01\",\n    \"day_02\",\n]\n"
+        );
+    }
+
+    #[test]
+    fn test_add_member_no_workspace() {
+        let content = "[package]\nname = \"aocrs\"\n";
+        assert!(add_member(content, "day_02", 2, &Config::default())
+            .unwrap()
+            .is_none());
+    }
+}
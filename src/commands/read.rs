@@ -0,0 +1,155 @@
+use scraper::{ElementRef, Html, Node, Selector};
+
+use super::client::{build_with_defaults, check_response, AOC_URL};
+use super::workspace::day_dir;
+use crate::error::{AocError, Result};
+
+/// Fetch the puzzle description and write it as `README.md` to the day's directory,
+/// or print it if `print` is set
+pub async fn read_puzzle(year: u32, day: u32, print: bool) -> Result<()> {
+    let client = build_with_defaults();
+    let url = format!("{}/{}/day/{}", AOC_URL, year, day);
+    let resp = check_response(client.get(url).send().await?, &year, &day)?;
+    let markdown = to_markdown(&resp.text().await?)?;
+
+    if print {
+        print!("{}", markdown);
+        return Ok(());
+    }
+    let dir = day_dir(&day);
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join("README.md");
+    std::fs::write(&path, markdown)?;
+    println!("Wrote {}", path.display());
+    Ok(())
+}
+
+/// Convert the parts of the puzzle description into Markdown
+pub fn to_markdown(html: &str) -> Result<String> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("article.day-desc")?;
+    let blocks: Vec<String> = document
+        .select(&selector)
+        .flat_map(|article| article.children().filter_map(ElementRef::wrap).map(block))
+        .filter(|b| !b.is_empty())
+        .collect();
+    if blocks.is_empty() {
+        return Err(AocError::Parse("No puzzle description found".to_string()));
+    }
+    Ok(blocks.join("\n\n") + "\n")
+}
+
+fn block(element: ElementRef) -> String {
+    match element.value().name() {
+        "h1" | "h2" | "h3" => {
+            let level = element.value().name()[1..].parse().unwrap_or(2);
+            let title = inline(element);
+            format!(
+                "{} {}",
+                "#".repeat(level),
+                title.trim().trim_matches('-').trim()
+            )
+        }
+        "pre" => {
+            let code: String = element.text().collect();
+            let newline = if code.ends_with('\n') { "" } else { "\n" };
+            format!("```\n{}{}```", code, newline)
+        }
+        "ul" | "ol" => {
+            let ordered = element.value().name() == "ol";
+            element
+                .children()
+                .filter_map(ElementRef::wrap)
+                .filter(|li| li.value().name() == "li")
+                .enumerate()
+                .map(|(i, li)| {
+                    let bullet = if ordered {
+                        format!("{}.", i + 1)
+                    } else {
+                        "-".to_string()
+                    };
+                    format!("{} {}", bullet, collapse(&inline(li)))
+                })
+                .collect::<Vec<String>>()
+                .join("\n")
+        }
+        _ => collapse(&inline(element)),
+    }
+}
+
+fn inline(element: ElementRef) -> String {
+    let mut out = String::new();
+    for child in element.children() {
+        match child.value() {
+            Node::Text(text) => out.push_str(text),
+            Node::Element(_) => {
+                let child = ElementRef::wrap(child).unwrap();
+                let content = inline(child);
+                match child.value().name() {
+                    "code" => {
+                        let code: String = child.text().collect();
+                        let code = format!("`{}`", code);
+                        let emphasised = child
+                            .descendants()
+                            .filter_map(ElementRef::wrap)
+                            .any(|e| e.value().name() == "em");
+                        if emphasised {
+                            out.push_str(&format!("**{}**", code));
+                        } else {
+                            out.push_str(&code);
+                        }
+                    }
+                    "em" if content.starts_with('`') => out.push_str(&format!("**{}**", content)),
+                    "em" => out.push_str(&format!("*{}*", content)),
+                    "a" => {
+                        let href = child.value().attr("href").unwrap_or_default();
+                        let href = if href.starts_with('/') {
+                            format!("{}{}", AOC_URL, href)
+                        } else {
+                            href.to_string()
+                        };
+                        out.push_str(&format!("[{}]({})", content, href));
+                    }
+                    _ => out.push_str(&content),
+                }
+            }
+            _ => {}
+        }
+    }
+    out
+}
+
+/// Collapse the line breaks of HTML source into single spaces
+fn collapse(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_markdown() {
+        let html = r#"<main><article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2>
+<p>Something is <em>wrong</em> with
+<a href="/2023/about">global snow production</a>.</p>
+<pre><code>1abc2
+pqr3stu8vwx
+</code></pre>
+<ul><li>In <code>1abc2</code>, it is <code><em>12</em></code>.</li><li>Second</li></ul>
+</article></main>"#;
+        assert_eq!(
+            to_markdown(html).unwrap(),
+            "## Day 1: Trebuchet?!\n\n\
+             Something is *wrong* with [global snow production](https://adventofcode.com/2023/about).\n\n\
+             ```\n1abc2\npqr3stu8vwx\n```\n\n\
+             - In `1abc2`, it is **`12`**.\n\
+             - Second\n"
+        );
+    }
+
+    #[test]
+    fn test_to_markdown_no_description() {
+        assert!(to_markdown("<html></html>").is_err());
+    }
+}
@@ -0,0 +1,193 @@
+use std::ffi::OsStr;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use super::config::{self, INPUT_FILE_ENV, TEST_FILE_ENV};
+use super::workspace::{day_dir, day_name, find_root};
+use crate::error::Result;
+
+/// Answers printed by a solution as `Part 1: ...` and `Part 2: ...`
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Answers {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Answers {
+    pub fn get(&self, part: u8) -> Option<&String> {
+        match part {
+            1 => self.part_1.as_ref(),
+            2 => self.part_2.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// Build and run the solution of the given day in its own directory
+pub fn run_day(day: u32, test: bool, release: bool) -> Result<Answers> {
+    let output = solution(day, test, release)?
+        .stderr(Stdio::inherit())
+        .output()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    for line in stdout.lines().filter(|line| !is_answer(line)) {
+        println!("{}", line);
+    }
+    if !output.status.success() {
+        return Err(format!("{} failed: {}", day_name(&day), output.status).into());
+    }
+    Ok(parse_answers(&stdout))
+}
+
+/// Command running the solution of the given day, building it first if needed
+pub fn solution(day: u32, test: bool, release: bool) -> Result<Command> {
+    let mut command = cargo(day, "run", release)?;
+    if test {
+        command.args(["--", "--test"]);
+    }
+    Ok(command)
+}
+
+/// Build the solution of the given day without running it
+pub fn build_day(day: u32, release: bool) -> Result<()> {
+    let status = cargo(day, "build", release)?.status()?;
+    if !status.success() {
+        return Err(format!("Building {} failed: {}", day_name(&day), status).into());
+    }
+    Ok(())
+}
+
+/// Command running the built binary of the given day directly, without going through cargo
+pub fn binary(day: u32, release: bool) -> Result<Command> {
+    let target = match std::env::var_os("CARGO_TARGET_DIR") {
+        Some(dir) => PathBuf::from(dir),
+        None => find_root().unwrap_or_default().join("target"),
+    };
+    let profile = if release { "release" } else { "debug" };
+    let binary = target.join(profile).join(format!(
+        "{}{}",
+        day_name(&day),
+        std::env::consts::EXE_SUFFIX
+    ));
+    if !binary.exists() {
+        return Err(format!("{} isn't built", binary.display()).into());
+    }
+    in_day_dir(day, binary)
+}
+
+fn cargo(day: u32, subcommand: &str, release: bool) -> Result<Command> {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let mut command = in_day_dir(day, cargo)?;
+    command.args(cargo_args(
+        day,
+        subcommand,
+        release,
+        config::get().offline.value,
+    ));
+    Ok(command)
+}
+
+/// Arguments of cargo, which mustn't reach the network either when aocrs is offline
+fn cargo_args(day: u32, subcommand: &str, release: bool, offline: bool) -> Vec<String> {
+    let mut args = vec![
+        subcommand.to_string(),
+        "--quiet".to_string(),
+        "--package".to_string(),
+        day_name(&day),
+    ];
+    if release {
+        args.push("--release".to_string());
+    }
+    if offline {
+        args.push("--offline".to_string());
+    }
+    args
+}
+
+fn in_day_dir(day: u32, program: impl AsRef<OsStr>) -> Result<Command> {
+    let dir = day_dir(&day);
+    if !dir.exists() {
+        return Err(format!(
+            "{} doesn't exist, create it with `aocrs new {}`",
+            dir.display(),
+            day
+        )
+        .into());
+    }
+
+    let config = config::get();
+    let mut command = Command::new(program);
+    command
+        .current_dir(&dir)
+        // Tell the solution which input files to read, see `utils::Args::input_file_path`
+        .env(INPUT_FILE_ENV, &config.input_file.value)
+        .env(TEST_FILE_ENV, &config.test_file.value);
+    Ok(command)
+}
+
+fn is_answer(line: &str) -> bool {
+    line.starts_with("Part 1:") || line.starts_with("Part 2:")
+}
+
+pub fn parse_answers(stdout: &str) -> Answers {
+    let mut answers = Answers::default();
+    for line in stdout.lines() {
+        if let Some(answer) = line.strip_prefix("Part 1:") {
+            answers.part_1 = Some(answer.trim().to_string());
+        } else if let Some(answer) = line.strip_prefix("Part 2:") {
+            answers.part_2 = Some(answer.trim().to_string());
+        }
+    }
+    answers
+}
+
+pub fn print_answers(day: u32, answers: &Answers) {
+    println!("Day {:02}", day);
+    for part in 1..=2 {
+        let answer = answers.get(part).map_or("-", |a| a.as_str());
+        println!("  Part {}: {}", part, answer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cargo_args() {
+        assert_eq!(
+            cargo_args(3, "build", false, false),
+            ["build", "--quiet", "--package", "day_03"]
+        );
+        assert_eq!(
+            cargo_args(3, "run", true, true),
+            [
+                "run",
+                "--quiet",
+                "--package",
+                "day_03",
+                "--release",
+                "--offline"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_answers() {
+        let stdout = "Seeds: [79, 14]\nPart 1: 35\nThat took: 1ms\nPart 2:  46 \n";
+        assert_eq!(
+            parse_answers(stdout),
+            Answers {
+                part_1: Some("35".to_string()),
+                part_2: Some("46".to_string()),
+            }
+        );
+        assert_eq!(
+            parse_answers("Part 1: 35"),
+            Answers {
+                part_1: Some("35".to_string()),
+                part_2: None,
+            }
+        );
+    }
+}
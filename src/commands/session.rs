@@ -0,0 +1,225 @@
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use reqwest::header::CACHE_CONTROL;
+use scraper::{Html, Selector};
+
+use super::client::AocClient;
+use super::config;
+use crate::error::Result;
+
+/// Environment variable checked for the session token before the session file
+pub const SESSION_ENV: &str = "AOC_SESSION";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionSource {
+    Env,
+    File,
+}
+
+impl fmt::Display for SessionSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Env => write!(f, "environment variable {}", SESSION_ENV),
+            Self::File => write!(f, "session file"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Session {
+    pub token: String,
+    pub source: SessionSource,
+}
+
+impl Session {
+    pub fn cookie(&self) -> String {
+        format!("session={}", self.token)
+    }
+
+    /// Token with everything but the last four characters hidden
+    pub fn masked(&self) -> String {
+        // Count characters rather than bytes, a pasted token isn't necessarily ASCII
+        let chars: Vec<char> = self.token.chars().collect();
+        let hidden = chars.len().saturating_sub(4);
+        let shown: String = chars[hidden..].iter().collect();
+        format!("{}{}", "*".repeat(hidden.min(8)), shown)
+    }
+}
+
+/// Path of the stored session token, `<config dir>/aocrs/session` unless configured
+pub fn session_file() -> PathBuf {
+    config::get().session_file.value.clone()
+}
+
+/// Load the session token from the environment or the session file, in that order
+pub fn load() -> Option<Session> {
+    if let Some(token) = std::env::var(SESSION_ENV).ok().and_then(clean_token) {
+        return Some(Session {
+            token,
+            source: SessionSource::Env,
+        });
+    }
+    let content = fs::read_to_string(session_file()).ok()?;
+    clean_token(content).map(|token| Session {
+        token,
+        source: SessionSource::File,
+    })
+}
+
+/// Accepts the bare token or the whole `session=...` cookie copied from the browser
+fn clean_token(token: String) -> Option<String> {
+    let token = token.trim();
+    let token = token.strip_prefix("session=").unwrap_or(token);
+    if token.is_empty() {
+        return None;
+    }
+    Some(token.to_string())
+}
+
+pub fn set(token: String) -> Result<PathBuf> {
+    let token = clean_token(token).ok_or("Session token can't be empty")?;
+    let path = session_file();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    write_private(&path, &token)?;
+    Ok(path)
+}
+
+/// Write a file only its owner can read, without it ever being readable by others
+fn write_private(path: &Path, content: &str) -> io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    // The mode only applies to new files, an existing one is restricted before writing to it
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(content.as_bytes())
+}
+
+pub fn clear() -> Result<Option<PathBuf>> {
+    let path = session_file();
+    if !path.exists() {
+        return Ok(None);
+    }
+    fs::remove_file(&path)?;
+    Ok(Some(path))
+}
+
+/// Check whether the client is logged in by probing the event page for the user name
+pub async fn validate(client: &AocClient, base_url: &str, year: &u32) -> Result<bool> {
+    let url = format!("{}/{}", base_url, year);
+    let resp = client
+        .get(url)
+        .header(CACHE_CONTROL, "no-cache")
+        .send()
+        .await?;
+    if !resp.status().is_success() {
+        return Ok(false);
+    }
+
+    Ok(is_logged_in(&resp.text().await?))
+}
+
+/// Whether the page was served to a logged in user, which has their name in the header
+pub fn is_logged_in(html: &str) -> bool {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("header div.user").unwrap();
+    document.select(&selector).next().is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest_middleware::ClientBuilder;
+
+    use super::*;
+    use crate::commands::client::{base_client, ClientOptions};
+
+    fn session(token: &str) -> Session {
+        Session {
+            token: token.to_string(),
+            source: SessionSource::Env,
+        }
+    }
+
+    #[test]
+    fn test_clean_token() {
+        assert_eq!(clean_token("  abc\n".to_string()), Some("abc".to_string()));
+        assert_eq!(
+            clean_token("session=abc".to_string()),
+            Some("abc".to_string())
+        );
+        assert_eq!(clean_token(" \n".to_string()), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mode = |path: &Path| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+
+        let new = dir.path().join("new");
+        write_private(&new, "abc").unwrap();
+        assert_eq!(mode(&new), 0o600);
+
+        let existing = dir.path().join("existing");
+        fs::write(&existing, "old token").unwrap();
+        fs::set_permissions(&existing, fs::Permissions::from_mode(0o644)).unwrap();
+        write_private(&existing, "abc").unwrap();
+        assert_eq!(mode(&existing), 0o600);
+        assert_eq!(fs::read_to_string(&existing).unwrap(), "abc");
+    }
+
+    #[test]
+    fn test_masked() {
+        assert_eq!(session("0123456789abcdef").masked(), "********cdef");
+        assert_eq!(session("abc").masked(), "abc");
+        assert_eq!(session("tokén→✓ü").masked(), "****n→✓ü");
+    }
+
+    #[tokio::test]
+    async fn test_validate_logged_in() {
+        let mut server = mockito::Server::new_async().await;
+        let mock = server
+            .mock("GET", "/2023")
+            .match_header("cookie", "session=abc")
+            .match_header(
+                "user-agent",
+                mockito::Matcher::Regex("^aocrs/.+ \\(https://".into()),
+            )
+            .with_body(r#"<header><div class="user">bwian</div></header>"#)
+            .create_async()
+            .await;
+
+        let user_agent = ClientOptions::default().user_agent();
+        let client = ClientBuilder::new(base_client(Some(&session("abc")), &user_agent)).build();
+        assert!(validate(&client, &server.url(), &2023).await.unwrap());
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_validate_logged_out() {
+        let mut server = mockito::Server::new_async().await;
+        server
+            .mock("GET", "/2023")
+            .with_body(r#"<header><a href="/2023/auth/login">[Log In]</a></header>"#)
+            .create_async()
+            .await;
+
+        let client = ClientBuilder::new(base_client(None, "aocrs")).build();
+        assert!(!validate(&client, &server.url(), &2023).await.unwrap());
+    }
+}
@@ -0,0 +1,216 @@
+use std::collections::BTreeMap;
+
+use scraper::{Html, Selector};
+use serde::Serialize;
+
+use super::client::{build_with_defaults, AocClient, AOC_URL};
+use super::session::is_logged_in;
+use crate::error::{AocError, Result};
+
+/// Both parts of each day on the personal stats page
+type PersonalStats = BTreeMap<u32, (Option<PartStats>, Option<PartStats>)>;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct DayStats {
+    pub day: u32,
+    pub stars: u8,
+    pub part_1: Option<PartStats>,
+    pub part_2: Option<PartStats>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PartStats {
+    /// Time from unlock to solving, as shown by Advent of Code, e.g. `01:15:00` or `>24h`
+    pub time: String,
+    pub rank: u32,
+    pub score: u32,
+}
+
+pub async fn show_stats(year: u32, json: bool) -> Result<()> {
+    let client = build_with_defaults();
+    let calendar = fetch(&client, &format!("{}/{}", AOC_URL, year)).await?;
+    let personal = fetch(&client, &format!("{}/{}/leaderboard/self", AOC_URL, year)).await?;
+    let stats = merge(parse_calendar(&calendar)?, parse_personal_stats(&personal)?);
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+    } else {
+        print!("{}", render(&stats));
+    }
+    Ok(())
+}
+
+async fn fetch(client: &AocClient, url: &str) -> Result<String> {
+    let resp = client.get(url).send().await?;
+    if !resp.status().is_success() {
+        return Err(AocError::Http(resp.status()));
+    }
+    Ok(resp.text().await?)
+}
+
+/// Stars per day from the event calendar
+pub fn parse_calendar(html: &str) -> Result<BTreeMap<u32, u8>> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("pre.calendar a[aria-label]")?;
+    let mut stars = BTreeMap::new();
+    for day in document.select(&selector) {
+        // e.g. "Day 1", "Day 1, one star" or "Day 1, two stars"
+        let label = day.value().attr("aria-label").unwrap_or_default();
+        let (day, count) = label.split_once(',').unwrap_or((label, ""));
+        let Some(day) = day.trim().strip_prefix("Day ").and_then(|d| d.parse().ok()) else {
+            continue;
+        };
+        let count = match count.trim() {
+            "one star" => 1,
+            "two stars" => 2,
+            _ => 0,
+        };
+        stars.insert(day, count);
+    }
+    Ok(stars)
+}
+
+/// Times, ranks and scores per day from the personal stats page
+pub fn parse_personal_stats(html: &str) -> Result<PersonalStats> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("main article pre")?;
+    let Some(table) = document.select(&selector).next() else {
+        // The stats are only shown to logged in users, and only once they have a star
+        return if is_logged_in(html) {
+            Ok(PersonalStats::new())
+        } else {
+            Err(AocError::Unauthenticated)
+        };
+    };
+
+    let text: String = table.text().collect();
+    let mut stats = BTreeMap::new();
+    for line in text.lines() {
+        let columns: Vec<&str> = line.split_whitespace().collect();
+        let Some(day) = columns.first().and_then(|d| d.parse().ok()) else {
+            continue;
+        };
+        let part_1 = columns.get(1..4).and_then(parse_part);
+        let part_2 = columns.get(4..7).and_then(parse_part);
+        stats.insert(day, (part_1, part_2));
+    }
+    Ok(stats)
+}
+
+fn parse_part(columns: &[&str]) -> Option<PartStats> {
+    Some(PartStats {
+        time: columns[0].to_string(),
+        rank: columns[1].parse().ok()?,
+        score: columns[2].parse().ok()?,
+    })
+}
+
+fn merge(stars: BTreeMap<u32, u8>, mut personal: PersonalStats) -> Vec<DayStats> {
+    stars
+        .into_iter()
+        .map(|(day, stars)| {
+            let (part_1, part_2) = personal.remove(&day).unwrap_or_default();
+            DayStats {
+                day,
+                stars,
+                part_1,
+                part_2,
+            }
+        })
+        .collect()
+}
+
+pub fn render(stats: &[DayStats]) -> String {
+    let mut out = format!(
+        "Day  Stars  {:>9} {:>7}  {:>9} {:>7}\n",
+        "Part 1", "Rank", "Part 2", "Rank"
+    );
+    for day in stats {
+        let (time_1, rank_1) = part_columns(&day.part_1);
+        let (time_2, rank_2) = part_columns(&day.part_2);
+        out.push_str(&format!(
+            "{:>3}  {:<5}  {:>9} {:>7}  {:>9} {:>7}\n",
+            day.day,
+            "*".repeat(day.stars as usize),
+            time_1,
+            rank_1,
+            time_2,
+            rank_2
+        ));
+    }
+    let total: u32 = stats.iter().map(|d| d.stars as u32).sum();
+    out.push_str(&format!("Total stars: {}\n", total));
+    out
+}
+
+fn part_columns(part: &Option<PartStats>) -> (String, String) {
+    match part {
+        Some(part) => (part.time.clone(), part.rank.to_string()),
+        None => ("-".to_string(), "-".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CALENDAR: &str = include_str!("../../tests/fixtures/calendar.html");
+    const STATS: &str = include_str!("../../tests/fixtures/stats.html");
+    const STATS_NO_STARS: &str = include_str!("../../tests/fixtures/stats_no_stars.html");
+
+    #[test]
+    fn test_parse_calendar() {
+        assert_eq!(
+            parse_calendar(CALENDAR).unwrap(),
+            BTreeMap::from([(1, 2), (2, 1), (3, 0)])
+        );
+    }
+
+    #[test]
+    fn test_parse_personal_stats() {
+        let stats = parse_personal_stats(STATS).unwrap();
+        assert_eq!(stats.len(), 2);
+        assert_eq!(
+            stats[&1].1,
+            Some(PartStats {
+                time: "01:00:00".to_string(),
+                rank: 3210,
+                score: 0
+            })
+        );
+        assert_eq!(stats[&2].0.as_ref().unwrap().rank, 9876);
+        assert_eq!(stats[&2].1, None);
+    }
+
+    #[test]
+    fn test_parse_personal_stats_logged_out() {
+        assert!(matches!(
+            parse_personal_stats("<main><article><p>Log in</p></article></main>"),
+            Err(AocError::Unauthenticated)
+        ));
+    }
+
+    #[test]
+    fn test_parse_personal_stats_no_stars() {
+        assert_eq!(
+            parse_personal_stats(STATS_NO_STARS).unwrap(),
+            PersonalStats::new()
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let stats = merge(
+            parse_calendar(CALENDAR).unwrap(),
+            parse_personal_stats(STATS).unwrap(),
+        );
+        assert_eq!(
+            render(&stats),
+            "Day  Stars     Part 1    Rank     Part 2    Rank\n  \
+             1  **      00:30:00    4321   01:00:00    3210\n  \
+             2  *       01:15:00    9876          -       -\n  \
+             3                 -       -          -       -\n\
+             Total stars: 3\n"
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use std::fmt;
+use std::time::Duration;
+
+use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
+
+use super::client::{build_with_defaults, check_response, AocClient, AOC_URL};
+use super::ledger::{ledger_path, Ledger};
+use crate::error::{AocError, Result};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// An answer was submitted too recently, with the time left to wait if given
+    RateLimited(Option<Duration>),
+    /// The part was already solved, or part 1 of the day wasn't solved yet
+    #[serde(alias = "AlreadySolved")]
+    WrongLevel,
+}
+
+impl Verdict {
+    /// Whether the answer itself was found to be incorrect
+    pub fn is_wrong(&self) -> bool {
+        matches!(self, Self::TooHigh | Self::TooLow | Self::Wrong)
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Correct => write!(f, "That's the right answer!"),
+            Self::TooHigh => write!(f, "Wrong answer, too high"),
+            Self::TooLow => write!(f, "Wrong answer, too low"),
+            Self::Wrong => write!(f, "Wrong answer"),
+            Self::RateLimited(Some(wait)) => {
+                write!(f, "Answered too recently, wait {}s", wait.as_secs())
+            }
+            Self::RateLimited(None) => write!(f, "Answered too recently"),
+            Self::WrongLevel => write!(f, "Part already solved or not unlocked yet"),
+        }
+    }
+}
+
+pub async fn submit(year: u32, day: u32, part: u8, answer: &str) -> Result<Verdict> {
+    let ledger_path = ledger_path();
+    let mut ledger = Ledger::load(&ledger_path)?;
+    ledger.check(&year, &day, &part, answer)?;
+
+    let client = build_with_defaults();
+    let verdict = post_answer(&client, AOC_URL, &year, &day, &part, answer).await?;
+    ledger.record(year, day, part, answer, verdict.clone());
+    ledger.save(&ledger_path)?;
+    Ok(verdict)
+}
+
+pub async fn post_answer(
+    client: &AocClient,
+    base_url: &str,
+    year: &u32,
+    day: &u32,
+    part: &u8,
+    answer: &str,
+) -> Result<Verdict> {
+    let url = format!("{}/{}/day/{}/answer", base_url, year, day);
+    let level = part.to_string();
+    let resp = client
+        .post(url)
+        .form(&[("level", level.as_str()), ("answer", answer)])
+        .send()
+        .await?;
+    let resp = check_response(resp, year, day)?;
+    parse_verdict(&resp.text().await?)
+}
+
+pub fn parse_verdict(html: &str) -> Result<Verdict> {
+    let document = Html::parse_document(html);
+    let selector = Selector::parse("main article")?;
+    let text: String = document
+        .select(&selector)
+        .next()
+        .ok_or(AocError::Parse("No answer node found".to_string()))?
+        .text()
+        .collect();
+
+    let verdict = if text.contains("That's the right answer") {
+        Verdict::Correct
+    } else if text.contains("your answer is too high") {
+        Verdict::TooHigh
+    } else if text.contains("your answer is too low") {
+        Verdict::TooLow
+    } else if text.contains("That's not the right answer") {
+        Verdict::Wrong
+    } else if text.contains("You gave an answer too recently") {
+        Verdict::RateLimited(parse_wait(&text))
+    } else if text.contains("Did you already complete it?") {
+        Verdict::WrongLevel
+    } else {
+        return Err(AocError::Parse(format!(
+            "Unrecognised answer response: {}",
+            text.trim()
+        )));
+    };
+    Ok(verdict)
+}
+
+/// Parse the wait time from e.g. "You have 1m 2s left to wait."
+fn parse_wait(text: &str) -> Option<Duration> {
+    let (before, _) = text.split_once("left to wait")?;
+    let (_, wait) = before.rsplit_once("You have ")?;
+    let mut seconds: u64 = 0;
+    for part in wait.split_whitespace() {
+        let (value, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+        let value: u64 = value.parse().ok()?;
+        seconds += match unit {
+            "h" => value * 3600,
+            "m" => value * 60,
+            "s" => value,
+            _ => return None,
+        };
+    }
+    Some(Duration::from_secs(seconds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(article: &str) -> String {
+        format!(
+            "<html><body><main><article><p>{}</p></article></main></body></html>",
+            article
+        )
+    }
+
+    #[test]
+    fn test_parse_verdict_correct() {
+        let html = page("That's the right answer!  You are <em>one gold star</em> closer.");
+        assert_eq!(parse_verdict(&html).unwrap(), Verdict::Correct);
+    }
+
+    #[test]
+    fn test_parse_verdict_wrong() {
+        let html = page("That's not the right answer; your answer is too high.  Please wait one minute before trying again.");
+        assert_eq!(parse_verdict(&html).unwrap(), Verdict::TooHigh);
+        let html = page("That's not the right answer; your answer is too low.");
+        assert_eq!(parse_verdict(&html).unwrap(), Verdict::TooLow);
+        let html = page("That's not the right answer.  If you're stuck, make sure you're using the full input data.");
+        assert_eq!(parse_verdict(&html).unwrap(), Verdict::Wrong);
+    }
+
+    #[test]
+    fn test_parse_verdict_rate_limited() {
+        let html = page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 2s left to wait. <a href=\"/2023/day/1\">[Return to Day 1]</a>");
+        assert_eq!(
+            parse_verdict(&html).unwrap(),
+            Verdict::RateLimited(Some(Duration::from_secs(62)))
+        );
+    }
+
+    #[test]
+    fn test_parse_verdict_wrong_level() {
+        let html =
+            page("You don't seem to be solving the right level.  Did you already complete it?");
+        assert_eq!(parse_verdict(&html).unwrap(), Verdict::WrongLevel);
+    }
+
+    #[test]
+    fn test_parse_verdict_unknown() {
+        assert!(parse_verdict(&page("Bwian")).is_err());
+    }
+}
@@ -0,0 +1,224 @@
+use std::io::Write;
+use std::time::Duration;
+
+use chrono::{DateTime, Datelike, FixedOffset, TimeZone, Utc};
+
+use crate::error::{AocError, Result};
+
+const FIRST_YEAR: u32 = 2015;
+const LAST_DAY: u32 = 25;
+
+/// Source of the current time, replaceable in tests
+#[async_trait::async_trait]
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+
+    async fn sleep(&self, duration: Duration);
+}
+
+pub struct SystemClock;
+
+#[async_trait::async_trait]
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    async fn sleep(&self, duration: Duration) {
+        tokio::time::sleep(duration).await
+    }
+}
+
+/// Puzzles unlock at midnight US Eastern time, which in December is always UTC-5
+pub fn unlock_time(year: u32, day: u32) -> DateTime<Utc> {
+    let eastern = FixedOffset::west_opt(5 * 3600).unwrap();
+    eastern
+        .with_ymd_and_hms(year as i32, 12, day, 0, 0, 0)
+        .unwrap()
+        .with_timezone(&Utc)
+}
+
+/// Reject days that don't exist and years whose event hasn't started yet
+pub fn validate_day(year: u32, day: u32, clock: &dyn Clock) -> Result<()> {
+    validate(year, day, clock, chrono::Duration::zero())
+}
+
+/// Reject days that don't exist and years whose first puzzle doesn't unlock within `ahead`
+fn validate(year: u32, day: u32, clock: &dyn Clock, ahead: chrono::Duration) -> Result<()> {
+    if !(1..=LAST_DAY).contains(&day) {
+        return Err(format!("Day must be between 1 and {}, got {}", LAST_DAY, day).into());
+    }
+    // Checking the year first keeps an out of range year from overflowing the date
+    if year < FIRST_YEAR
+        || year > clock.now().year() as u32
+        || clock.now() + ahead < unlock_time(year, 1)
+    {
+        return Err(format!("No Advent of Code event in {}", year).into());
+    }
+    Ok(())
+}
+
+/// Like [`validate_day`], but also reject puzzles that aren't unlocked yet
+pub fn validate_unlocked(year: u32, day: u32, clock: &dyn Clock) -> Result<()> {
+    validate_day(year, day, clock)?;
+    if clock.now() < unlock_time(year, day) {
+        return Err(AocError::NotUnlocked { year, day });
+    }
+    Ok(())
+}
+
+/// Wait until the puzzle unlocks, showing a countdown
+///
+/// The first puzzle of an event can be waited for from the day before it unlocks.
+pub async fn wait_for_unlock(year: u32, day: u32, clock: &dyn Clock) -> Result<()> {
+    validate(year, day, clock, chrono::Duration::days(1))?;
+    let unlock = unlock_time(year, day);
+    let mut waited = false;
+    while let Ok(left) = (unlock - clock.now()).to_std() {
+        if left.is_zero() {
+            break;
+        }
+        let seconds = left.as_secs();
+        print!(
+            "\rDay {} unlocks in {:02}:{:02}:{:02} ",
+            day,
+            seconds / 3600,
+            seconds % 3600 / 60,
+            seconds % 60
+        );
+        std::io::stdout().flush()?;
+        waited = true;
+
+        // Sleep to the next full second so the countdown ticks evenly
+        let step = match left.subsec_millis() {
+            0 => Duration::from_secs(1),
+            millis => Duration::from_millis(millis as u64),
+        };
+        clock.sleep(step).await;
+    }
+    if waited {
+        println!();
+    }
+    Ok(())
+}
+
+/// Retry while the puzzle isn't available yet, doubling the delay after each attempt
+pub async fn retry_locked<T, F, Fut>(
+    clock: &dyn Clock,
+    retries: u32,
+    delay: Duration,
+    mut f: F,
+) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T>>,
+{
+    let mut delay = delay;
+    for _ in 0..retries {
+        match f().await {
+            Err(AocError::NotUnlocked { .. }) => {
+                clock.sleep(delay).await;
+                delay *= 2;
+            }
+            result => return result,
+        }
+    }
+    f().await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    struct FakeClock {
+        now: Mutex<DateTime<Utc>>,
+        sleeps: Mutex<u32>,
+    }
+
+    impl FakeClock {
+        fn at(rfc3339: &str) -> Self {
+            Self {
+                now: Mutex::new(DateTime::parse_from_rfc3339(rfc3339).unwrap().into()),
+                sleeps: Mutex::new(0),
+            }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl Clock for FakeClock {
+        fn now(&self) -> DateTime<Utc> {
+            *self.now.lock().unwrap()
+        }
+
+        async fn sleep(&self, duration: Duration) {
+            *self.now.lock().unwrap() += chrono::Duration::from_std(duration).unwrap();
+            *self.sleeps.lock().unwrap() += 1;
+        }
+    }
+
+    #[test]
+    fn test_unlock_time() {
+        assert_eq!(
+            unlock_time(2023, 1),
+            DateTime::parse_from_rfc3339("2023-12-01T05:00:00Z").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        let clock = FakeClock::at("2023-12-05T04:59:59Z");
+        assert!(validate_day(2023, 0, &clock).is_err());
+        assert!(validate_day(2023, 26, &clock).is_err());
+        assert!(validate_day(2014, 1, &clock).is_err());
+        assert!(validate_day(2024, 1, &clock).is_err());
+        assert!(validate_day(i32::MAX as u32, 1, &clock).is_err());
+        assert!(validate_unlocked(2023, 4, &clock).is_ok());
+        assert!(matches!(
+            validate_unlocked(2023, 5, &clock),
+            Err(AocError::NotUnlocked { year: 2023, day: 5 })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_validate_before_event() {
+        let clock = FakeClock::at("2026-03-01T12:00:00Z");
+        assert!(validate_day(2025, 25, &clock).is_ok());
+        assert!(validate_day(2026, 1, &clock).is_err());
+        assert!(wait_for_unlock(2026, 1, &clock).await.is_err());
+        assert_eq!(*clock.sleeps.lock().unwrap(), 0);
+
+        // The evening before the event starts
+        let clock = FakeClock::at("2026-11-30T23:00:00Z");
+        assert!(validate_day(2026, 1, &clock).is_err());
+        wait_for_unlock(2026, 1, &clock).await.unwrap();
+        assert_eq!(clock.now(), unlock_time(2026, 1));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_unlock() {
+        let clock = FakeClock::at("2023-12-05T04:59:57.500Z");
+        wait_for_unlock(2023, 5, &clock).await.unwrap();
+        assert_eq!(clock.now(), unlock_time(2023, 5));
+        assert_eq!(*clock.sleeps.lock().unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_locked() {
+        let clock = FakeClock::at("2023-12-05T05:00:00Z");
+        let mut attempts = 0;
+        let result = retry_locked(&clock, 5, Duration::from_secs(1), || {
+            attempts += 1;
+            let result = if attempts < 3 {
+                Err(AocError::NotUnlocked { year: 2023, day: 5 })
+            } else {
+                Ok(attempts)
+            };
+            async move { result }
+        })
+        .await;
+        assert_eq!(result.unwrap(), 3);
+        assert_eq!(*clock.sleeps.lock().unwrap(), 2);
+    }
+}
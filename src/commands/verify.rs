@@ -0,0 +1,306 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use super::config;
+use super::inputs::{Expected, EXPECTED_FILE};
+use super::ledger::{ledger_path, Ledger};
+use super::run::{binary, build_day, parse_answers, Answers};
+use super::workspace::day_dir;
+use crate::error::{AocError, Result};
+
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// Known answers of a day for the example and the personal input, stored in `answers.toml`
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KnownAnswers {
+    #[serde(default)]
+    pub test: Expected,
+    #[serde(default)]
+    pub input: Expected,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    Fail {
+        expected: String,
+        actual: Option<String>,
+    },
+    /// No known answer to compare to
+    Unknown(Option<String>),
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pass => write!(f, "pass"),
+            Self::Fail { expected, actual } => write!(
+                f,
+                "FAIL (expected {}, got {})",
+                expected,
+                actual.as_deref().unwrap_or("nothing")
+            ),
+            Self::Unknown(actual) => write!(f, "unknown ({})", actual.as_deref().unwrap_or("-")),
+        }
+    }
+}
+
+/// Result of running the solution on one input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Check {
+    Ran {
+        elapsed: Duration,
+        parts: [Outcome; 2],
+    },
+    Skipped(String),
+    Failed(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayReport {
+    pub day: u32,
+    pub test: Check,
+    pub input: Check,
+}
+
+impl DayReport {
+    fn outcomes(&self) -> impl Iterator<Item = &Outcome> {
+        [&self.test, &self.input]
+            .into_iter()
+            .filter_map(|check| match check {
+                Check::Ran { parts, .. } => Some(parts.iter()),
+                _ => None,
+            })
+            .flatten()
+    }
+
+    /// Whether the solution failed to run or gave a wrong answer
+    pub fn failed(&self) -> bool {
+        matches!(self.test, Check::Failed(_))
+            || matches!(self.input, Check::Failed(_))
+            || self
+                .outcomes()
+                .any(|outcome| matches!(outcome, Outcome::Fail { .. }))
+    }
+}
+
+/// Run the solutions of the days on their example and personal inputs and compare the
+/// answers to the known ones
+pub fn verify(days: &[u32], release: bool, save: bool) -> Result<()> {
+    let year = config::get().year.value;
+    let ledger = Ledger::load(&ledger_path())?;
+    let mut reports = vec![];
+    for day in days {
+        let dir = day_dir(day);
+        let known = known_answers(&dir, &ledger, year, *day)?;
+        if save {
+            fs::write(dir.join(ANSWERS_FILE), toml::to_string(&known)?)?;
+        }
+
+        build_day(*day, release)?;
+        let report = DayReport {
+            day: *day,
+            test: check(&dir, *day, true, release, &known.test),
+            input: check(&dir, *day, false, release, &known.input),
+        };
+        print!("{}", render(&report));
+        reports.push(report);
+    }
+
+    let failed = reports.iter().filter(|r| r.failed()).count();
+    println!(
+        "{} of {} days verified",
+        reports.len() - failed,
+        reports.len()
+    );
+    if failed > 0 {
+        return Err(format!("Verification of {} days failed", failed).into());
+    }
+    Ok(())
+}
+
+/// Answers from `answers.toml`, completed by the example answers in `expected.toml` and the
+/// correct answers in the submit ledger
+pub fn known_answers(dir: &Path, ledger: &Ledger, year: u32, day: u32) -> Result<KnownAnswers> {
+    let mut known: KnownAnswers = match fs::read_to_string(dir.join(ANSWERS_FILE)) {
+        Ok(content) => toml::from_str(&content)
+            .map_err(|e| AocError::Parse(format!("{}: {}", dir.join(ANSWERS_FILE).display(), e)))?,
+        Err(_) => KnownAnswers::default(),
+    };
+    if let Ok(content) = fs::read_to_string(dir.join(EXPECTED_FILE)) {
+        let expected: Expected = toml::from_str(&content).map_err(|e| {
+            AocError::Parse(format!("{}: {}", dir.join(EXPECTED_FILE).display(), e))
+        })?;
+        known.test.part_1 = known.test.part_1.or(expected.part_1);
+        known.test.part_2 = known.test.part_2.or(expected.part_2);
+    }
+    let solved = |part| {
+        ledger
+            .get(&year, &day, &part)
+            .filter(|record| record.solved)
+            .and_then(|record| record.answer.clone())
+    };
+    known.input.part_1 = known.input.part_1.or_else(|| solved(1));
+    known.input.part_2 = known.input.part_2.or_else(|| solved(2));
+    Ok(known)
+}
+
+fn check(dir: &Path, day: u32, test: bool, release: bool, expected: &Expected) -> Check {
+    let config = config::get();
+    let file = match test {
+        true => &config.test_file.value,
+        false => &config.input_file.value,
+    };
+    if !dir.join(file).exists() {
+        return Check::Skipped(format!("no {}", file));
+    }
+
+    // Run the binary built by `build_day` so that cargo's startup isn't part of the timing
+    let start = Instant::now();
+    let output = match binary(day, release).and_then(|mut command| {
+        if test {
+            command.arg("--test");
+        }
+        Ok(command
+            // Keep the panic message as the last line of the output
+            .env("RUST_BACKTRACE", "0")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output()?)
+    }) {
+        Ok(output) => output,
+        Err(e) => return Check::Failed(e.to_string()),
+    };
+    let elapsed = start.elapsed();
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let reason = stderr
+            .lines()
+            .rev()
+            .find(|line| !line.trim().is_empty() && !line.starts_with("note:"));
+        return Check::Failed(reason.unwrap_or("failed").trim().to_string());
+    }
+
+    let answers = parse_answers(&String::from_utf8_lossy(&output.stdout));
+    Check::Ran {
+        elapsed,
+        parts: compare(expected, &answers),
+    }
+}
+
+/// Compare the answers of both parts to the expected ones
+pub fn compare(expected: &Expected, answers: &Answers) -> [Outcome; 2] {
+    [(1, &expected.part_1), (2, &expected.part_2)].map(|(part, expected)| {
+        let actual = answers.get(part).cloned();
+        match expected {
+            None => Outcome::Unknown(actual),
+            Some(expected) if actual.as_deref() == Some(expected.trim()) => Outcome::Pass,
+            Some(expected) => Outcome::Fail {
+                expected: expected.trim().to_string(),
+                actual,
+            },
+        }
+    })
+}
+
+pub fn render(report: &DayReport) -> String {
+    let mut out = format!("Day {:02}\n", report.day);
+    for (name, check) in [("test", &report.test), ("input", &report.input)] {
+        let line = match check {
+            Check::Ran { elapsed, parts } => format!(
+                "{:>8.3}s  Part 1: {}  Part 2: {}",
+                elapsed.as_secs_f64(),
+                parts[0],
+                parts[1]
+            ),
+            Check::Skipped(reason) => format!("skipped, {}", reason),
+            Check::Failed(reason) => format!("FAILED: {}", reason),
+        };
+        out.push_str(&format!("  {:<5}  {}\n", name, line));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::submit::Verdict;
+
+    fn expected(part_1: Option<&str>, part_2: Option<&str>) -> Expected {
+        Expected {
+            part_1: part_1.map(String::from),
+            part_2: part_2.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_compare() {
+        let answers = parse_answers("Part 1: 142\nPart 2: 280\n");
+        assert_eq!(
+            compare(&expected(Some("142"), Some("281")), &answers),
+            [
+                Outcome::Pass,
+                Outcome::Fail {
+                    expected: "281".to_string(),
+                    actual: Some("280".to_string())
+                }
+            ]
+        );
+        assert_eq!(
+            compare(
+                &expected(None, Some("281")),
+                &parse_answers("Part 1: 142\n")
+            ),
+            [
+                Outcome::Unknown(Some("142".to_string())),
+                Outcome::Fail {
+                    expected: "281".to_string(),
+                    actual: None
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_known_answers() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(ANSWERS_FILE), "[input]\npart_1 = \"55\"\n").unwrap();
+        fs::write(
+            dir.path().join(EXPECTED_FILE),
+            "part_1 = \"142\"\npart_2 = \"281\"\n",
+        )
+        .unwrap();
+        let mut ledger = Ledger::default();
+        ledger.record(2023, 1, 1, "54", Verdict::Correct);
+        ledger.record(2023, 1, 2, "56", Verdict::Correct);
+
+        let known = known_answers(dir.path(), &ledger, 2023, 1).unwrap();
+        assert_eq!(known.test, expected(Some("142"), Some("281")));
+        // answers.toml takes precedence over the ledger
+        assert_eq!(known.input, expected(Some("55"), Some("56")));
+    }
+
+    #[test]
+    fn test_render() {
+        let report = DayReport {
+            day: 1,
+            test: Check::Ran {
+                elapsed: Duration::from_millis(1500),
+                parts: [Outcome::Pass, Outcome::Unknown(None)],
+            },
+            input: Check::Skipped("no input.txt".to_string()),
+        };
+        assert_eq!(
+            render(&report),
+            "Day 01\n  \
+             test      1.500s  Part 1: pass  Part 2: unknown (-)\n  \
+             input  skipped, no input.txt\n"
+        );
+        assert!(!report.failed());
+    }
+}
@@ -0,0 +1,96 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use toml_edit::DocumentMut;
+
+use super::config::{self, Config};
+use crate::error::Result;
+
+/// Closest directory, starting from the current one, with a workspace manifest
+pub fn find_root() -> Option<PathBuf> {
+    let cwd = std::env::current_dir().ok()?;
+    cwd.ancestors()
+        .find(|dir| is_workspace_root(dir))
+        .map(Path::to_path_buf)
+}
+
+fn is_workspace_root(dir: &Path) -> bool {
+    fs::read_to_string(dir.join("Cargo.toml"))
+        .ok()
+        .and_then(|content| content.parse::<DocumentMut>().ok())
+        .is_some_and(|doc| doc.contains_key("workspace"))
+}
+
+/// Directory of the given day's crate, e.g. `day_05`, following the `day_dir` setting
+///
+/// Resolved against the workspace root if there is one, otherwise against the current
+/// directory, unless it already is the day's directory.
+pub fn day_dir(day: &u32) -> PathBuf {
+    let dir = PathBuf::from(config::get().day_dir(*day));
+    if let Some(root) = find_root() {
+        return root.join(dir);
+    }
+    let cwd = std::env::current_dir().unwrap_or_default();
+    if cwd.ends_with(&dir) {
+        return cwd;
+    }
+    dir
+}
+
+/// Package name of the given day's crate, the last component of its directory
+pub fn day_name(day: &u32) -> String {
+    let dir = config::get().day_dir(*day);
+    Path::new(&dir)
+        .file_name()
+        .map_or(dir.clone(), |name| name.to_string_lossy().to_string())
+}
+
+/// Days listed in the workspace members, in order
+pub fn days() -> Result<Vec<(u32, PathBuf)>> {
+    let root = find_root().ok_or("Not inside a Cargo workspace")?;
+    let content = fs::read_to_string(root.join("Cargo.toml"))?;
+    Ok(day_members(&content, config::get())?
+        .into_iter()
+        .map(|(day, member)| (day, root.join(member)))
+        .collect())
+}
+
+fn day_members(manifest: &str, config: &Config) -> Result<Vec<(u32, String)>> {
+    let doc: DocumentMut = manifest.parse()?;
+    let Some(members) = doc
+        .get("workspace")
+        .and_then(|w| w.get("members"))
+        .and_then(|m| m.as_array())
+    else {
+        return Ok(vec![]);
+    };
+    let mut days: Vec<(u32, String)> = members
+        .iter()
+        .filter_map(|m| m.as_str())
+        .filter_map(|m| {
+            let day = config.parse_day_dir(m)?;
+            Some((day, m.to_string()))
+        })
+        .collect();
+    days.sort();
+    Ok(days)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day_members() {
+        let manifest = "[workspace]\nmembers = [\"utils\", \"day_10\", \"day_02\", \"day_x\"]\n";
+        assert_eq!(
+            day_members(manifest, &Config::default()).unwrap(),
+            vec![(2, "day_02".to_string()), (10, "day_10".to_string())]
+        );
+        assert!(
+            day_members("[package]\nname = \"aocrs\"\n", &Config::default())
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
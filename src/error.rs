@@ -0,0 +1,174 @@
+use std::fmt;
+use std::io;
+use std::time::Duration;
+
+use reqwest::StatusCode;
+use scraper::error::SelectorErrorKind;
+
+pub type Result<T> = std::result::Result<T, AocError>;
+
+/// Errors of the aocrs commands
+///
+/// Each kind of error exits the process with its own code, see [`AocError::exit_code`].
+#[derive(Debug)]
+pub enum AocError {
+    /// Request failed to reach Advent of Code
+    Network(reqwest_middleware::Error),
+    /// Advent of Code responded with an unexpected status
+    Http(StatusCode),
+    /// No valid session token
+    Unauthenticated,
+    /// Puzzle of the day isn't released yet
+    NotUnlocked {
+        year: u32,
+        day: u32,
+    },
+    /// Page or file content wasn't what was expected
+    Parse(String),
+    Io(io::Error),
+    /// An answer was submitted too recently, with the time left to wait if known
+    RateLimited(Option<Duration>),
+    /// Offline and the page or file isn't available locally
+    Offline(String),
+    Other(String),
+}
+
+impl AocError {
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            Self::Other(_) => 1,
+            Self::Network(_) | Self::Http(_) => 3,
+            Self::Unauthenticated => 4,
+            Self::NotUnlocked { .. } => 5,
+            Self::Parse(_) => 6,
+            Self::Io(_) => 7,
+            Self::RateLimited(_) => 8,
+            Self::Offline(_) => 9,
+        }
+    }
+}
+
+impl fmt::Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Network(e) => write!(f, "Unable to reach Advent of Code: {}", e),
+            Self::Http(status) => write!(f, "Unexpected response from Advent of Code: {}", status),
+            Self::Unauthenticated => write!(
+                f,
+                "Not logged in, set a valid session token with `aocrs session set`"
+            ),
+            Self::NotUnlocked { year, day } => {
+                write!(f, "Puzzle of day {} of {} isn't unlocked yet", day, year)
+            }
+            Self::Parse(message) => write!(f, "Unable to parse: {}", message),
+            Self::Io(e) => write!(f, "{}", e),
+            Self::RateLimited(Some(wait)) => {
+                write!(f, "Answered too recently, try again in {}s", wait.as_secs())
+            }
+            Self::RateLimited(None) => write!(f, "Answered too recently, try again later"),
+            Self::Offline(what) => write!(f, "Not available offline: {}", what),
+            Self::Other(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for AocError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Network(e) => Some(e),
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<reqwest_middleware::Error> for AocError {
+    fn from(e: reqwest_middleware::Error) -> Self {
+        match e {
+            // Errors of the aocrs middleware pass through the client as is
+            reqwest_middleware::Error::Middleware(e) => match e.downcast::<AocError>() {
+                Ok(e) => e,
+                Err(e) => Self::Network(e.into()),
+            },
+            e => Self::Network(e),
+        }
+    }
+}
+
+impl From<reqwest::Error> for AocError {
+    fn from(e: reqwest::Error) -> Self {
+        Self::Network(e.into())
+    }
+}
+
+impl From<io::Error> for AocError {
+    fn from(e: io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+impl From<cacache::Error> for AocError {
+    fn from(e: cacache::Error) -> Self {
+        Self::Io(io::Error::other(e))
+    }
+}
+
+impl From<serde_json::Error> for AocError {
+    fn from(e: serde_json::Error) -> Self {
+        Self::Parse(e.to_string())
+    }
+}
+
+impl From<toml::ser::Error> for AocError {
+    fn from(e: toml::ser::Error) -> Self {
+        Self::Parse(e.to_string())
+    }
+}
+
+impl From<toml_edit::TomlError> for AocError {
+    fn from(e: toml_edit::TomlError) -> Self {
+        Self::Parse(e.to_string())
+    }
+}
+
+impl From<SelectorErrorKind<'_>> for AocError {
+    fn from(e: SelectorErrorKind<'_>) -> Self {
+        Self::Parse(e.to_string())
+    }
+}
+
+impl From<String> for AocError {
+    fn from(message: String) -> Self {
+        Self::Other(message)
+    }
+}
+
+impl From<&str> for AocError {
+    fn from(message: &str) -> Self {
+        Self::Other(message.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_codes_distinct() {
+        let errors = [
+            AocError::Other("bwian".to_string()),
+            AocError::Http(StatusCode::INTERNAL_SERVER_ERROR),
+            AocError::Unauthenticated,
+            AocError::NotUnlocked { year: 2023, day: 1 },
+            AocError::Parse("bwian".to_string()),
+            AocError::Io(io::Error::from(io::ErrorKind::NotFound)),
+            AocError::RateLimited(None),
+            AocError::Offline("bwian".to_string()),
+        ];
+        let mut codes: Vec<u8> = errors.iter().map(|e| e.exit_code()).collect();
+        codes.sort();
+        codes.dedup();
+        assert_eq!(codes.len(), errors.len());
+        assert!(!codes.contains(&0) && !codes.contains(&2));
+    }
+}
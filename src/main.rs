@@ -1,30 +1,315 @@
 mod commands;
+mod error;
+
+use std::process::ExitCode;
 
 use clap::{Parser, Subcommand};
 
-const YEAR: u32 = 2023;
+use commands::cache;
+use commands::client::{build_with_defaults, AOC_URL};
+use commands::config;
+use commands::inputs::InputOptions;
+use commands::session;
+use commands::submit::Verdict;
+use commands::unlock::{validate_day, validate_unlocked, SystemClock};
+use error::{AocError, Result};
 
 #[derive(Debug, Parser)]
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+    /// Only use cached pages and saved files, never the network
+    #[arg(long, global = true)]
+    offline: bool,
 }
 
 #[derive(Debug, Subcommand)]
 enum Commands {
+    /// Time the release builds of solutions on their inputs
+    #[command(group(clap::ArgGroup::new("days").required(true)))]
+    Bench {
+        #[arg(group = "days")]
+        day: Option<u32>,
+        /// Benchmark every day in the workspace
+        #[arg(long, group = "days")]
+        all: bool,
+        /// Number of times to run each solution
+        #[arg(long, default_value_t = 10)]
+        runs: usize,
+    },
+    /// Inspect and manage the cached responses of Advent of Code
+    Cache {
+        #[command(subcommand)]
+        action: CacheAction,
+    },
+    /// Inspect the aocrs configuration
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
     /// Load inputs of a given day
-    Inputs { day: u32, year: Option<u32> },
+    Inputs {
+        day: u32,
+        year: Option<u32>,
+        /// Number of the example block to save as test.txt
+        #[arg(long, value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..))]
+        example: Option<usize>,
+        /// Fetch the puzzle page again instead of using the cache
+        #[arg(long)]
+        refresh: bool,
+        /// Wait for the puzzle to unlock
+        #[arg(long)]
+        wait: bool,
+    },
+    /// Show a private leaderboard
+    Leaderboard {
+        id: u64,
+        year: Option<u32>,
+        /// Show the completion times of the given day
+        #[arg(long)]
+        day: Option<u32>,
+    },
+    /// Create the crate of a new day from the boilerplate
+    New {
+        day: u32,
+        year: Option<u32>,
+        /// Also download the inputs of the day
+        #[arg(long)]
+        inputs: bool,
+    },
+    /// Save the puzzle description of a given day as Markdown
+    Read {
+        day: u32,
+        year: Option<u32>,
+        /// Print to the terminal instead of writing README.md
+        #[arg(long)]
+        print: bool,
+    },
+    /// Build and run the solution of a given day
+    Run {
+        day: u32,
+        /// Run with the example input
+        #[arg(long)]
+        test: bool,
+        /// Build in release mode
+        #[arg(long)]
+        release: bool,
+    },
+    /// Manage the Advent of Code session token
+    Session {
+        #[command(subcommand)]
+        action: SessionAction,
+    },
+    /// Show personal stars, times and ranks of each day
+    Stats {
+        year: Option<u32>,
+        /// Print as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Submit an answer to a part of a given day
+    Submit {
+        day: u32,
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        answer: String,
+        year: Option<u32>,
+    },
+    /// Check the answers of solutions against the known ones
+    #[command(group(clap::ArgGroup::new("days").required(true)))]
+    Verify {
+        #[arg(group = "days")]
+        day: Option<u32>,
+        /// Verify every day in the workspace
+        #[arg(long, group = "days")]
+        all: bool,
+        /// Build in release mode
+        #[arg(long)]
+        release: bool,
+        /// Store the known answers in answers.toml of each day
+        #[arg(long)]
+        save: bool,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+enum CacheAction {
+    /// Print the directory of the cache
+    Path,
+    /// List the cached responses
+    List,
+    /// Remove the cached responses of a day, or all of them
+    Clear {
+        /// Only remove the puzzle page and input of the given day
+        #[arg(long)]
+        day: Option<u32>,
+        /// Year of the day to remove
+        #[arg(long, requires = "day")]
+        year: Option<u32>,
+    },
+    /// Remove stale pages and responses that shouldn't have been cached
+    Prune,
+}
+
+#[derive(Debug, Subcommand)]
+enum ConfigAction {
+    /// Show the effective settings and where each of them came from
+    Show,
+}
+
+#[derive(Debug, Subcommand)]
+enum SessionAction {
+    /// Store the session token in the user config directory
+    Set { token: String },
+    /// Show the session token in use and check that it is still valid
+    Show,
+    /// Remove the stored session token
+    Clear,
 }
 
 #[tokio::main]
-async fn main() -> Result<(), Box<dyn std::error::Error>> {
+async fn main() -> ExitCode {
     let cli: Cli = Cli::parse();
 
+    match run(cli).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::from(e.exit_code())
+        }
+    }
+}
+
+async fn run(cli: Cli) -> Result<()> {
+    let mut config = config::Config::load()?;
+    config.set_offline(cli.offline);
+    let config = config::init(config);
+
     match cli.command {
-        Commands::Inputs { day, year } => {
-            commands::inputs::load_inputs(year.unwrap_or(YEAR), day).await?
+        Commands::Bench { day, all: _, runs } => {
+            commands::bench::bench(&selected_days(day)?, runs)?
+        }
+        Commands::Cache { action } => {
+            let path = cache::cache_path();
+            match action {
+                CacheAction::Path => println!("{}", path.display()),
+                CacheAction::List => print!("{}", cache::render(&cache::entries(&path)?)),
+                CacheAction::Clear { day, year } => {
+                    let day = match day {
+                        Some(day) => {
+                            let year = config.year_or(year).value;
+                            validate_day(year, day, &SystemClock)?;
+                            Some((year, day))
+                        }
+                        None => None,
+                    };
+                    let removed = cache::clear(&path, day)?;
+                    println!("Removed {} cached responses", removed);
+                }
+                CacheAction::Prune => {
+                    let removed = cache::prune(&path).await?;
+                    println!("Removed {} cached responses", removed);
+                }
+            }
+        }
+        Commands::Config { action } => match action {
+            ConfigAction::Show => print!("{}", config.render()),
+        },
+        Commands::Inputs {
+            day,
+            year,
+            example,
+            refresh,
+            wait,
+        } => {
+            let options = InputOptions {
+                example,
+                refresh,
+                wait,
+            };
+            commands::inputs::load_inputs(config.year_or(year).value, day, &options).await?
+        }
+        Commands::Leaderboard { id, year, day } => {
+            let year = config.year_or(year).value;
+            if let Some(day) = day {
+                validate_day(year, day, &SystemClock)?;
+            }
+            commands::leaderboard::show_leaderboard(year, id, day).await?
+        }
+        Commands::New { day, year, inputs } => {
+            let year = config.year_or(year).value;
+            validate_day(year, day, &SystemClock)?;
+            commands::new::new_day(year, day, inputs).await?
         }
+        Commands::Read { day, year, print } => {
+            let year = config.year_or(year).value;
+            validate_unlocked(year, day, &SystemClock)?;
+            commands::read::read_puzzle(year, day, print).await?
+        }
+        Commands::Run { day, test, release } => {
+            let answers = commands::run::run_day(day, test, release)?;
+            commands::run::print_answers(day, &answers);
+        }
+        Commands::Session { action } => match action {
+            SessionAction::Set { token } => {
+                let path = session::set(token)?;
+                println!("Session token stored in {}", path.display());
+            }
+            SessionAction::Show => match session::load() {
+                Some(s) => {
+                    println!("Session token {} from {}", s.masked(), s.source);
+                    if config.offline.value {
+                        println!("Logged in: unknown while offline");
+                    } else {
+                        let client = build_with_defaults();
+                        let valid = session::validate(&client, AOC_URL, &config.year.value).await?;
+                        println!("Logged in: {}", valid);
+                    }
+                }
+                None => println!("No session token set"),
+            },
+            SessionAction::Clear => match session::clear()? {
+                Some(path) => println!("Removed {}", path.display()),
+                None => println!("No stored session token"),
+            },
+        },
+        Commands::Stats { year, json } => {
+            let year = config.year_or(year).value;
+            validate_day(year, 1, &SystemClock)?;
+            commands::stats::show_stats(year, json).await?
+        }
+        Commands::Submit {
+            day,
+            part,
+            answer,
+            year,
+        } => {
+            let year = config.year_or(year).value;
+            validate_unlocked(year, day, &SystemClock)?;
+            let verdict = commands::submit::submit(year, day, part, answer.trim()).await?;
+            if let Verdict::RateLimited(wait) = verdict {
+                return Err(AocError::RateLimited(wait));
+            }
+            println!("{}", verdict);
+        }
+        Commands::Verify {
+            day,
+            all: _,
+            release,
+            save,
+        } => commands::verify::verify(&selected_days(day)?, release, save)?,
     };
 
     Ok(())
 }
+
+/// The given day, or every day in the workspace
+fn selected_days(day: Option<u32>) -> Result<Vec<u32>> {
+    match day {
+        Some(day) => Ok(vec![day]),
+        None => Ok(commands::workspace::days()?
+            .into_iter()
+            .map(|(day, _)| day)
+            .collect()),
+    }
+}
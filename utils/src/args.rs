@@ -0,0 +1,259 @@
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use log::LevelFilter;
+
+const USAGE: &str = "Usage: [--test] [--input <path>] [--part 1|2] [--example N] [-v|-q] [--stdin] [--log-file <path>]";
+
+/// Command line arguments shared by the solutions of every day
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Args {
+    /// Use the example input and log debug messages
+    pub test: bool,
+    /// Read the input from the given file
+    pub input: Option<PathBuf>,
+    /// Only solve the given part
+    pub part: Option<u8>,
+    /// Use the example of the given number, see [`Args::input_file_path`]
+    pub example: Option<usize>,
+    /// Number of `-v` minus number of `-q`
+    pub verbosity: i8,
+    /// Read the input from the standard input
+    pub stdin: bool,
+    /// Write the log messages to the given file instead of the standard output
+    pub log_file: Option<PathBuf>,
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct ArgsError(String);
+
+debug_as_display!(ArgsError);
+
+impl fmt::Display for ArgsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}\n{}", self.0, USAGE)
+    }
+}
+
+impl Error for ArgsError {}
+
+impl Args {
+    /// Arguments the program was started with
+    pub fn from_env() -> Result<Self, ArgsError> {
+        Self::parse(env::args().skip(1))
+    }
+
+    pub fn parse<I, S>(args: I) -> Result<Self, ArgsError>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut parsed = Self::default();
+        let mut args = args.into_iter().map(Into::into);
+        while let Some(arg) = args.next() {
+            // Long options take their value either as the next argument or after `=`
+            let (name, inline) = match arg.split_once('=') {
+                Some((name, value)) if name.starts_with("--") => (name, Some(value.to_string())),
+                _ => (arg.as_str(), None),
+            };
+            let mut value = || {
+                inline
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| ArgsError(format!("{} needs a value", name)))
+            };
+            match name {
+                "--test" => parsed.test = true,
+                "--stdin" => parsed.stdin = true,
+                "--input" => parsed.input = Some(PathBuf::from(value()?)),
+                "--log-file" => parsed.log_file = Some(PathBuf::from(value()?)),
+                "--part" => {
+                    parsed.part = match value()?.as_str() {
+                        "1" => Some(1),
+                        "2" => Some(2),
+                        part => return Err(ArgsError(format!("No part {}", part))),
+                    }
+                }
+                "--example" => {
+                    let example = value()?;
+                    parsed.example = match example.parse() {
+                        Ok(n) if n > 0 => Some(n),
+                        _ => return Err(ArgsError(format!("No example {}", example))),
+                    }
+                }
+                "--verbose" => parsed.verbosity += 1,
+                "--quiet" => parsed.verbosity -= 1,
+                // Short flags can be combined, e.g. `-vv`
+                _ if name.len() > 1
+                    && name.starts_with('-')
+                    && name[1..].chars().all(|c| c == 'v' || c == 'q') =>
+                {
+                    for c in name[1..].chars() {
+                        parsed.verbosity += if c == 'v' { 1 } else { -1 };
+                    }
+                }
+                _ => return Err(ArgsError(format!("Unknown argument {}", arg))),
+            }
+        }
+        if parsed.stdin && parsed.input.is_some() {
+            return Err(ArgsError(
+                "--stdin and --input can't be used together".into(),
+            ));
+        }
+        Ok(parsed)
+    }
+
+    /// Whether an example is used, with `--test` or `--example`
+    pub fn is_test(&self) -> bool {
+        self.test || self.example.is_some()
+    }
+
+    /// Whether the given part should be solved
+    pub fn runs(&self, part: u8) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+
+    /// Input file of the given part, unless reading from the standard input
+    ///
+    /// Examples are numbered by their files: `test.txt` is the first one, and `test_pt2.txt`
+    /// the second one, which is the example of part 2 when it has its own. `aocrs run`
+    /// passes the file names configured for the workspace in the `AOCRS_TEST_FILE` and
+    /// `AOCRS_INPUT_FILE` environment variables.
+    pub fn input_file_path(&self, part: u8) -> Option<PathBuf> {
+        if self.stdin {
+            return None;
+        }
+        if let Some(input) = &self.input {
+            return Some(input.clone());
+        }
+        if !self.is_test() {
+            return Some(env_path("AOCRS_INPUT_FILE", "input.txt"));
+        }
+        let test = env_path("AOCRS_TEST_FILE", "test.txt");
+        Some(test_file_path(test, self.example, part))
+    }
+
+    pub fn log_level(&self) -> LevelFilter {
+        let levels = [
+            LevelFilter::Off,
+            LevelFilter::Error,
+            LevelFilter::Warn,
+            LevelFilter::Info,
+            LevelFilter::Debug,
+            LevelFilter::Trace,
+        ];
+        let base = if self.is_test() { 4 } else { 3 };
+        let level = (base + self.verbosity as isize).clamp(0, levels.len() as isize - 1);
+        levels[level as usize]
+    }
+}
+
+fn env_path(var: &str, default: &str) -> PathBuf {
+    PathBuf::from(env::var(var).unwrap_or_else(|_| default.to_string()))
+}
+
+fn test_file_path(test: PathBuf, example: Option<usize>, part: u8) -> PathBuf {
+    match example {
+        Some(n) => example_path(&test, n),
+        // Part 2 uses the first example unless it has one of its own
+        None => Some(example_path(&test, part as usize))
+            .filter(|path| path.exists())
+            .unwrap_or(test),
+    }
+}
+
+/// File of the example of the given number, e.g. `test.txt` for 1 and `test_pt2.txt` for 2
+fn example_path(test: &Path, n: usize) -> PathBuf {
+    if n <= 1 {
+        return test.to_path_buf();
+    }
+    let stem = test.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match test.extension() {
+        Some(ext) => format!("{}_pt{}.{}", stem, n, ext.to_string_lossy()),
+        None => format!("{}_pt{}", stem, n),
+    };
+    test.with_file_name(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let args = Args::parse(["--test", "--part", "2", "-vv", "--input=data.txt"]).unwrap();
+        assert_eq!(
+            args,
+            Args {
+                test: true,
+                input: Some(PathBuf::from("data.txt")),
+                part: Some(2),
+                example: None,
+                verbosity: 2,
+                stdin: false,
+                log_file: None,
+            }
+        );
+        assert!(!args.runs(1));
+        assert!(args.runs(2));
+        assert_eq!(args.log_level(), LevelFilter::Trace);
+
+        let args = Args::parse(["--example", "2", "-q"]).unwrap();
+        assert!(args.is_test());
+        assert!(args.runs(1));
+        assert_eq!(args.log_level(), LevelFilter::Info);
+        assert_eq!(Args::parse(["-q"]).unwrap().log_level(), LevelFilter::Warn);
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(Args::parse(["bwian"]).is_err());
+        assert!(Args::parse(["--part", "3"]).is_err());
+        assert!(Args::parse(["--part"]).is_err());
+        assert!(Args::parse(["--example", "0"]).is_err());
+        assert!(Args::parse(["--stdin", "--input", "data.txt"]).is_err());
+    }
+
+    #[test]
+    fn test_input_file_path() {
+        let path = |args: &[&str], part| {
+            Args::parse(args.iter().copied())
+                .unwrap()
+                .input_file_path(part)
+        };
+        assert_eq!(path(&[], 2), Some(PathBuf::from("input.txt")));
+        assert_eq!(path(&["--test"], 1), Some(PathBuf::from("test.txt")));
+        // No test_pt2.txt here
+        assert_eq!(path(&["--test"], 2), Some(PathBuf::from("test.txt")));
+        assert_eq!(
+            path(&["--example", "2"], 1),
+            Some(PathBuf::from("test_pt2.txt"))
+        );
+        assert_eq!(
+            path(&["--test", "--input", "data.txt"], 1),
+            Some(PathBuf::from("data.txt"))
+        );
+        assert_eq!(path(&["--stdin"], 1), None);
+    }
+
+    #[test]
+    fn test_test_file_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        let test = dir.join("test.txt");
+        assert_eq!(test_file_path(test.clone(), None, 2), test);
+
+        fs::write(dir.join("test_pt2.txt"), "1\n").unwrap();
+        assert_eq!(test_file_path(test.clone(), None, 1), test);
+        assert_eq!(
+            test_file_path(test.clone(), None, 2),
+            dir.join("test_pt2.txt")
+        );
+        assert_eq!(test_file_path(test.clone(), Some(1), 2), test);
+        assert_eq!(test_file_path(test, Some(3), 1), dir.join("test_pt3.txt"));
+    }
+}
@@ -0,0 +1,202 @@
+use std::env;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use crate::Args;
+
+/// Why the input of a solution couldn't be loaded
+pub enum InputError {
+    Missing(PathBuf),
+    /// File, or the standard input when there is no path
+    Unreadable(Option<PathBuf>, io::Error),
+    Empty(Option<PathBuf>),
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = |path: &Option<PathBuf>| match path {
+            Some(path) => path.display().to_string(),
+            None => "standard input".to_string(),
+        };
+        match self {
+            Self::Missing(path) => write!(
+                f,
+                "Input file {} doesn't exist, download it with `aocrs inputs {}`",
+                path.display(),
+                day()
+            ),
+            Self::Unreadable(path, e) => write!(f, "Unable to read {}: {}", name(path), e),
+            Self::Empty(None) => write!(f, "Standard input is empty"),
+            Self::Empty(path) => write!(
+                f,
+                "Input file {} is empty, download it again with `aocrs inputs {}`",
+                name(path),
+                day()
+            ),
+        }
+    }
+}
+
+debug_as_display!(InputError);
+
+impl Error for InputError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Unreadable(_, e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Day of the running solution, from the number its binary ends with, e.g. `day_01`
+fn day() -> String {
+    env::current_exe()
+        .ok()
+        .and_then(|exe| {
+            let stem = exe.file_stem()?.to_string_lossy().into_owned();
+            let digits = stem.trim_start_matches(|c: char| !c.is_ascii_digit());
+            digits.parse::<u32>().ok()
+        })
+        .map_or_else(|| "<day>".to_string(), |day| day.to_string())
+}
+
+fn read_file(filepath: &Path) -> Result<String, InputError> {
+    if !filepath.exists() {
+        return Err(InputError::Missing(filepath.to_path_buf()));
+    }
+    fs::read_to_string(filepath)
+        .map_err(|e| InputError::Unreadable(Some(filepath.to_path_buf()), e))
+}
+
+/// Standard input, read once and shared by both parts
+fn read_stdin() -> Result<String, InputError> {
+    static STDIN: OnceLock<Result<String, (io::ErrorKind, String)>> = OnceLock::new();
+    STDIN
+        .get_or_init(|| {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map(|_| input)
+                .map_err(|e| (e.kind(), e.to_string()))
+        })
+        .clone()
+        .map_err(|(kind, e)| InputError::Unreadable(None, io::Error::new(kind, e)))
+}
+
+fn read_raw(args: &Args, part: u8) -> Result<String, InputError> {
+    match args.input_file_path(part) {
+        Some(path) => read_file(&path),
+        None => read_stdin(),
+    }
+}
+
+/// Input with `\n` line endings and a single trailing newline, whatever the line endings
+/// of the file, e.g. CRLF from a Windows checkout
+pub fn normalize(input: &str) -> String {
+    let mut normalized = input.replace("\r\n", "\n");
+    normalized.truncate(normalized.trim_end_matches('\n').len());
+    normalized.push('\n');
+    normalized
+}
+
+/// Blocks of lines separated by one or more blank lines, with either line ending
+///
+/// The blocks don't include the line ending of their last line.
+pub fn blocks(input: &str) -> impl Iterator<Item = &str> {
+    let mut blocks = vec![];
+    let mut start = None;
+    let mut end = 0;
+    let mut offset = 0;
+    for line in input.split_inclusive('\n') {
+        let content = line.trim_end_matches(['\n', '\r']);
+        if content.trim().is_empty() {
+            if let Some(start) = start.take() {
+                blocks.push(&input[start..end]);
+            }
+        } else {
+            start.get_or_insert(offset);
+            end = offset + content.len();
+        }
+        offset += line.len();
+    }
+    if let Some(start) = start {
+        blocks.push(&input[start..end]);
+    }
+    blocks.into_iter()
+}
+
+/// Input of the first part, see [`try_read_part_input`]
+pub fn try_read_input(args: &Args) -> Result<String, InputError> {
+    try_read_part_input(args, 1)
+}
+
+/// Input of the given part, from the file selected by [`Args::input_file_path`] or from the
+/// standard input with `--stdin`, with normalized line endings
+pub fn try_read_part_input(args: &Args, part: u8) -> Result<String, InputError> {
+    let input = read_raw(args, part)?;
+    if input.trim().is_empty() {
+        return Err(InputError::Empty(args.input_file_path(part)));
+    }
+    Ok(normalize(&input))
+}
+
+/// Input of the first part, see [`read_part_input`]
+pub fn read_input(args: &Args) -> String {
+    read_part_input(args, 1)
+}
+
+/// Input of the given part with normalized line endings, panicking if it can't be read, see
+/// [`try_read_part_input`]
+pub fn read_part_input(args: &Args, part: u8) -> String {
+    normalize(&read_raw(args, part).unwrap_or_else(|e| panic!("{}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("1\r\n2\r\n\r\n3"), "1\n2\n\n3\n");
+        assert_eq!(normalize("1\n2\n\n\n"), "1\n2\n");
+    }
+
+    #[test]
+    fn test_blocks() {
+        let expected = vec!["RL", "AAA = (BBB, CCC)\nBBB = (DDD, EEE)"];
+        let lf = "RL\n\nAAA = (BBB, CCC)\nBBB = (DDD, EEE)\n";
+        assert_eq!(blocks(lf).collect::<Vec<_>>(), expected);
+
+        let crlf = "\r\nRL\r\n\r\n\r\nAAA = (BBB, CCC)\r\nBBB = (DDD, EEE)";
+        let crlf_blocks: Vec<_> = blocks(crlf).collect();
+        assert_eq!(crlf_blocks, ["RL", "AAA = (BBB, CCC)\r\nBBB = (DDD, EEE)"]);
+        assert_eq!(blocks("").count(), 0);
+    }
+
+    #[test]
+    fn test_try_read_input() {
+        let dir = tempfile::tempdir().unwrap();
+        let dir = dir.path();
+        let args = |file: &str| Args {
+            input: Some(dir.join(file)),
+            ..Args::default()
+        };
+
+        fs::write(dir.join("input.txt"), "1\r\n2\r\n").unwrap();
+        assert_eq!(try_read_input(&args("input.txt")).unwrap(), "1\n2\n");
+        assert_eq!(read_input(&args("input.txt")), "1\n2\n");
+
+        fs::write(dir.join("empty.txt"), "\n").unwrap();
+        assert!(matches!(
+            try_read_input(&args("empty.txt")),
+            Err(InputError::Empty(Some(_)))
+        ));
+        let missing = try_read_input(&args("missing.txt")).unwrap_err();
+        assert!(matches!(missing, InputError::Missing(_)));
+        assert!(missing.to_string().contains("aocrs inputs"));
+    }
+}
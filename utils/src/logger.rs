@@ -0,0 +1,214 @@
+use std::env;
+use std::fs::File;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Once, RwLock};
+use std::time::Instant;
+
+use log::{LevelFilter, Metadata, Record};
+
+use crate::Args;
+
+static INIT_LOGGING: Once = Once::new();
+
+/// Configuration of the installed logger, replaced on every [`Logger::init`]
+static STATE: RwLock<Option<State>> = RwLock::new(None);
+
+static GLOBAL_LOGGER: GlobalLogger = GlobalLogger;
+
+struct State {
+    logger: Logger,
+    start: Instant,
+    file: Option<File>,
+}
+
+/// Logger printing the time since it was set up, the level and the module of each message
+///
+/// Levels can be set per module with `RUST_LOG`-style filters, and messages can go to a file
+/// instead of the standard output so that they don't get mixed with the answers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Logger {
+    level: LevelFilter,
+    /// Levels of modules and their submodules
+    modules: Vec<(String, LevelFilter)>,
+    file: Option<PathBuf>,
+}
+
+impl Logger {
+    pub fn new(level: LevelFilter) -> Self {
+        Self {
+            level,
+            modules: vec![],
+            file: None,
+        }
+    }
+
+    /// Apply filters like `RUST_LOG`, e.g. `warn,day_06=debug`, ignoring invalid ones
+    pub fn filters(mut self, spec: &str) -> Self {
+        for directive in spec.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+            match directive.split_once('=') {
+                Some((module, level)) => match LevelFilter::from_str(level.trim()) {
+                    Ok(level) => self.modules.push((module.trim().to_string(), level)),
+                    Err(_) => eprintln!("Ignoring invalid log filter {}", directive),
+                },
+                None => match LevelFilter::from_str(directive) {
+                    Ok(level) => self.level = level,
+                    // A module alone logs everything
+                    Err(_) => self
+                        .modules
+                        .push((directive.to_string(), LevelFilter::Trace)),
+                },
+            }
+        }
+        self
+    }
+
+    /// Write the messages to the given file instead of the standard output
+    pub fn file(mut self, path: &Path) -> Self {
+        self.file = Some(path.to_path_buf());
+        self
+    }
+
+    /// Level of the most specific filter matching the target of a message
+    pub fn level_for(&self, target: &str) -> LevelFilter {
+        self.modules
+            .iter()
+            .filter(|(module, _)| {
+                target == module
+                    || target
+                        .strip_prefix(module.as_str())
+                        .is_some_and(|rest| rest.starts_with("::"))
+            })
+            .max_by_key(|(module, _)| module.len())
+            .map_or(self.level, |(_, level)| *level)
+    }
+
+    fn max_level(&self) -> LevelFilter {
+        self.modules
+            .iter()
+            .map(|(_, level)| *level)
+            .fold(self.level, Ord::max)
+    }
+
+    /// Install the logger, replacing the configuration of a previous one
+    pub fn init(self) -> io::Result<()> {
+        let file = match &self.file {
+            Some(path) => Some(File::create(path)?),
+            None => None,
+        };
+        // https://stackoverflow.com/a/43093371/14536215
+        INIT_LOGGING.call_once(|| {
+            log::set_logger(&GLOBAL_LOGGER).unwrap();
+        });
+        log::set_max_level(self.max_level());
+        *STATE.write().unwrap_or_else(|e| e.into_inner()) = Some(State {
+            logger: self,
+            start: Instant::now(),
+            file,
+        });
+        Ok(())
+    }
+}
+
+struct GlobalLogger;
+
+impl log::Log for GlobalLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        let state = STATE.read().unwrap_or_else(|e| e.into_inner());
+        state
+            .as_ref()
+            .is_some_and(|state| metadata.level() <= state.logger.level_for(metadata.target()))
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let state = STATE.read().unwrap_or_else(|e| e.into_inner());
+        let Some(state) = state.as_ref() else {
+            return;
+        };
+        let line = format!(
+            "{:>9.3}s {:<5} {}: {}\n",
+            state.start.elapsed().as_secs_f64(),
+            record.level(),
+            record.target(),
+            record.args()
+        );
+        // Losing a message isn't worth failing the solution
+        let _ = match &state.file {
+            Some(file) => {
+                let mut file: &File = file;
+                file.write_all(line.as_bytes())
+            }
+            None => io::stdout().write_all(line.as_bytes()),
+        };
+    }
+
+    fn flush(&self) {
+        let _ = io::stdout().flush();
+    }
+}
+
+/// Set up logging from the arguments, with the level of [`Args::log_level`] overridden by the
+/// filters in `RUST_LOG`
+pub fn set_logging_level(args: &Args) -> io::Result<()> {
+    let mut logger = Logger::new(args.log_level());
+    if let Ok(spec) = env::var("RUST_LOG") {
+        logger = logger.filters(&spec);
+    }
+    if let Some(path) = &args.log_file {
+        logger = logger.file(path);
+    }
+    logger.init()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use log::{debug, info};
+
+    use super::*;
+
+    #[test]
+    fn test_filters() {
+        let logger = Logger::new(LevelFilter::Info).filters("warn, day_06=debug,utils,bwian=loud");
+        assert_eq!(logger.level_for("day_01"), LevelFilter::Warn);
+        assert_eq!(logger.level_for("day_06"), LevelFilter::Debug);
+        assert_eq!(logger.level_for("day_06::race"), LevelFilter::Debug);
+        assert_eq!(logger.level_for("day_06_extra"), LevelFilter::Warn);
+        assert_eq!(logger.level_for("utils::solution"), LevelFilter::Trace);
+        assert_eq!(logger.level_for("bwian"), LevelFilter::Warn);
+        assert_eq!(logger.max_level(), LevelFilter::Trace);
+
+        let logger = Logger::new(LevelFilter::Info).filters("day_06=debug,day_06::race=error");
+        assert_eq!(logger.level_for("day_06::race"), LevelFilter::Error);
+        assert_eq!(logger.level_for("day_06::other"), LevelFilter::Debug);
+    }
+
+    #[test]
+    fn test_init_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("debug.log");
+
+        Logger::new(LevelFilter::Info).file(&path).init().unwrap();
+        info!("first");
+        debug!("hidden");
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains(" INFO  utils::logger::tests: first\n"));
+        assert!(!content.contains("hidden"));
+
+        // Initialising again replaces the configuration
+        Logger::new(LevelFilter::Warn)
+            .filters("utils::logger=debug")
+            .file(&path)
+            .init()
+            .unwrap();
+        debug!("second");
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains(" DEBUG utils::logger::tests: second\n"));
+        assert!(!content.contains("first"));
+    }
+}
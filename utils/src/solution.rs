@@ -0,0 +1,116 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use log::info;
+
+use crate::{set_logging_level, try_read_part_input, Args};
+
+/// Solution to the puzzle of one day
+///
+/// The input is parsed once and shared by both parts, see [`run`] for the entrypoint.
+pub trait Solution {
+    type Parsed;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Parsed, Box<dyn Error>>;
+
+    fn part_1(parsed: &Self::Parsed) -> Self::Answer1;
+
+    fn part_2(parsed: &Self::Parsed) -> Self::Answer2;
+}
+
+/// Answers of both parts, as printed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Answers {
+    pub part_1: String,
+    pub part_2: String,
+}
+
+/// Solve both parts of the input, for driving a solution from tests
+pub fn solve<S: Solution>(input: &str) -> Result<Answers, Box<dyn Error>> {
+    let parsed = S::parse(input)?;
+    Ok(Answers {
+        part_1: S::part_1(&parsed).to_string(),
+        part_2: S::part_2(&parsed).to_string(),
+    })
+}
+
+/// Entrypoint of a solution: reads the input selected by the arguments, sets up logging and
+/// prints the answers as `Part 1: ...` and `Part 2: ...` along with the time each part took
+///
+/// See [`Args`] for the arguments, e.g. `--part 2` only solves the second part. With
+/// `--test`, part 2 uses its own example from `test_pt2.txt` if there is one.
+pub fn run<S: Solution>() -> Result<(), Box<dyn Error>> {
+    let args = Args::from_env()?;
+    set_logging_level(&args)?;
+
+    let mut parsed: Option<(PathBuf, S::Parsed)> = None;
+    for part in [1, 2] {
+        if !args.runs(part) {
+            continue;
+        }
+        let path = args.input_file_path(part).unwrap_or_default();
+        if parsed
+            .as_ref()
+            .is_none_or(|(parsed_path, _)| *parsed_path != path)
+        {
+            let start = Instant::now();
+            parsed = Some((path, S::parse(&try_read_part_input(&args, part)?)?));
+            info!("Parsing took {:?}", start.elapsed());
+        }
+        let Some((_, input)) = &parsed else {
+            continue;
+        };
+
+        let start = Instant::now();
+        match part {
+            1 => println!("Part 1: {}", S::part_1(input)),
+            _ => println!("Part 2: {}", S::part_2(input)),
+        }
+        info!("Part {} took {:?}", part, start.elapsed());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Parsed = Vec<i32>;
+        type Answer1 = i32;
+        type Answer2 = String;
+
+        fn parse(input: &str) -> Result<Self::Parsed, Box<dyn Error>> {
+            Ok(input
+                .lines()
+                .map(|line| line.parse())
+                .collect::<Result<_, _>>()?)
+        }
+
+        fn part_1(parsed: &Self::Parsed) -> i32 {
+            parsed.iter().sum()
+        }
+
+        fn part_2(parsed: &Self::Parsed) -> String {
+            format!("{:?}", parsed.iter().max())
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(
+            solve::<Sum>("1\n2\n3\n").unwrap(),
+            Answers {
+                part_1: "6".to_string(),
+                part_2: "Some(3)".to_string()
+            }
+        );
+        assert!(solve::<Sum>("1\nbwian\n").is_err());
+    }
+}